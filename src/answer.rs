@@ -0,0 +1,127 @@
+use std::fmt;
+use num::{BigInt, ToPrimitive};
+
+/// The result of a single puzzle part. Keeps the width the solver produced so overflow-sized
+/// answers can be spotted, while still comparing equal across widths when the values match.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    I32(i32),
+    I64(i64),
+    U32(u32),
+    U64(u64),
+    Usize(usize),
+    U128(u128),
+    Big(BigInt),
+    Text(String),
+    /// The solver handled an input it can't answer, saying why
+    Unsolved(String)
+}
+
+impl Answer {
+    pub fn as_big_int(&self) -> Option<BigInt> {
+        match self {
+            Answer::I32(value) => Some(BigInt::from(*value)),
+            Answer::I64(value) => Some(BigInt::from(*value)),
+            Answer::U32(value) => Some(BigInt::from(*value)),
+            Answer::U64(value) => Some(BigInt::from(*value)),
+            Answer::Usize(value) => Some(BigInt::from(*value)),
+            Answer::U128(value) => Some(BigInt::from(*value)),
+            Answer::Big(value) => Some(value.clone()),
+            Answer::Text(_) | Answer::Unsolved(_) => None
+        }
+    }
+
+    /// True when the value no longer fits a 64 bit integer, signed or unsigned.
+    pub fn exceeds_64_bits(&self) -> bool {
+        match self.as_big_int() {
+            Some(value) => value.to_i64().is_none() && value.to_u64().is_none(),
+            None => false
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.as_big_int(), other.as_big_int()) {
+            (Some(left), Some(right)) => left == right,
+            _ => self.to_string() == other.to_string()
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::I32(value) => write!(f, "{}", value),
+            Answer::I64(value) => write!(f, "{}", value),
+            Answer::U32(value) => write!(f, "{}", value),
+            Answer::U64(value) => write!(f, "{}", value),
+            Answer::Usize(value) => write!(f, "{}", value),
+            Answer::U128(value) => write!(f, "{}", value),
+            Answer::Big(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Unsolved(reason) => write!(f, "unsolved: {}", reason),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($($source:ty => $variant:ident),*) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Self {
+                    Answer::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(i32 => I32, i64 => I64, u32 => U32, u64 => U64, usize => Usize, u128 => U128, BigInt => Big, String => Text);
+
+/// For solvers that can find an input has no answer, which they explain in the error.
+impl<T: Into<Answer>> From<Result<T, String>> for Answer {
+    fn from(result: Result<T, String>) -> Self {
+        result.map_or_else(Answer::Unsolved, Into::into)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigInt;
+    use crate::answer::Answer;
+
+    #[test]
+    fn answers_compare_across_widths() {
+        assert_eq!(Answer::from(42i32), Answer::from(42u64));
+        assert_eq!(Answer::from(42usize), Answer::from(BigInt::from(42)));
+        assert_ne!(Answer::from(-1i64), Answer::from(u64::MAX));
+    }
+
+    #[test]
+    fn text_answers_compare_by_display() {
+        assert_eq!(Answer::from("Merry Christmas!"), Answer::from("Merry Christmas!".to_string()));
+        assert_ne!(Answer::from("42"), Answer::from("43"));
+    }
+
+    #[test]
+    fn solver_errors_become_unsolved() {
+        assert_eq!(Answer::from(Ok::<u32, String>(7)), Answer::from(7u32));
+        assert_eq!(Answer::from(Err::<u32, String>("no rock".to_string())).to_string(), "unsolved: no rock");
+    }
+
+    #[test]
+    fn can_detect_oversized_answers() {
+        assert_eq!(Answer::from(167409079868000u128).exceeds_64_bits(), false);
+        assert_eq!(Answer::from(u128::MAX).exceeds_64_bits(), true);
+        assert_eq!(Answer::from(i64::MIN).exceeds_64_bits(), false);
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use toml::Value;
+use crate::error::ParseError;
+use crate::input::PuzzleInput;
+use crate::part_table::{read_days, write_days};
+use crate::solution::{Part, Puzzle};
+
+pub static DEFAULT_BASELINE_FILE: &str = "puzzle_inputs/bench_baseline.toml";
+
+/// Summary of a set of timings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 0 {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let seconds:Vec<f64> = sorted.iter().map(|sample| sample.as_secs_f64()).collect();
+        let mean = seconds.iter().sum::<f64>() / seconds.len() as f64;
+        let variance = seconds.iter().map(|sample| (sample - mean).powi(2)).sum::<f64>() / seconds.len() as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt())
+        }
+    }
+}
+
+/// Timings for one part, the input being parsed afresh before every solve.
+#[derive(Debug, Clone)]
+pub struct PartBench {
+    pub day: u32,
+    pub part: Part,
+    pub parse: Stats,
+    pub solve: Stats,
+    pub total: Stats
+}
+
+/// Runs the part `warmup` times without timing it, so caches and allocators settle, then times
+/// `iterations` runs.
+pub fn bench_part(solution: &dyn Puzzle, part: Part, input: &PuzzleInput, warmup: usize, iterations: usize) -> Result<PartBench, ParseError> {
+    for _ in 0..warmup {
+        solution.run(part, &input.content)?;
+    }
+
+    let mut parse_samples = Vec::with_capacity(iterations);
+    let mut solve_samples = Vec::with_capacity(iterations);
+    let mut total_samples = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations.max(1) {
+        let timer = Instant::now();
+        let parsed = solution.parse(&input.content)?;
+        let parse_time = timer.elapsed();
+
+        let timer = Instant::now();
+        black_box(solution.solve(part, black_box(&parsed)));
+        let solve_time = timer.elapsed();
+
+        parse_samples.push(parse_time);
+        solve_samples.push(solve_time);
+        total_samples.push(parse_time + solve_time);
+    }
+
+    Ok(PartBench {
+        day: solution.day(),
+        part,
+        parse: Stats::from_samples(&parse_samples),
+        solve: Stats::from_samples(&solve_samples),
+        total: Stats::from_samples(&total_samples)
+    })
+}
+
+/// Median times of an earlier run, kept in a toml file keyed by day and part:
+///
+/// ```toml
+/// [day01]
+/// part_one = 1427353
+/// ```
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: BTreeMap<(u32, Part), Duration>
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|error| format!("Could not read {}: {}", path.display(), error))?;
+        Baseline::parse(&content).map_err(|error| format!("Could not parse {}: {}", path.display(), error))
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let medians = read_days(content, "a time in nanoseconds", |value| match value {
+            Value::Integer(nanos) if *nanos >= 0 => Some(Duration::from_nanos(*nanos as u64)),
+            _ => None
+        })?;
+
+        Ok(Baseline { medians })
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<Duration> {
+        self.medians.get(&(day, part)).copied()
+    }
+
+    pub fn record(&mut self, bench: &PartBench) {
+        self.medians.insert((bench.day, bench.part), bench.total.median);
+    }
+
+    pub fn to_toml(&self) -> String {
+        write_days(&self.medians, |median| Value::Integer(median.as_nanos() as i64))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_toml()).map_err(|error| format!("Could not write {}: {}", path.display(), error))
+    }
+}
+
+/// How the median of a run moved against the baseline, as a fraction: 0.1 is ten percent slower.
+pub fn change_from(baseline: Duration, current: Duration) -> f64 {
+    (current.as_secs_f64() - baseline.as_secs_f64()) / baseline.as_secs_f64()
+}
+
+pub fn format_stats(stats: &Stats) -> String {
+    format!("min {:.2?}, median {:.2?}, mean {:.2?} ± {:.2?}", stats.min, stats.median, stats.mean, stats.stddev)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::answer::Answer;
+    use crate::bench::{Baseline, bench_part, change_from, PartBench, Stats};
+    use crate::error::ParseError;
+    use crate::input::PuzzleInput;
+    use crate::solution::{Part, Solution};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u64>;
+
+        fn day(&self) -> u32 {
+            1
+        }
+
+        fn title(&self) -> &'static str {
+            "Sum"
+        }
+
+        fn parse(&self, input: &str) -> Result<Vec<u64>, ParseError> {
+            input.split(',')
+                .map(|number| number.parse::<u64>().map_err(|_| ParseError::new(format!("{:?} is not a number", number))))
+                .collect()
+        }
+
+        fn part_one(&self, input: &Vec<u64>) -> Answer {
+            input.iter().sum::<u64>().into()
+        }
+
+        fn part_two(&self, input: &Vec<u64>) -> Answer {
+            input.iter().product::<u64>().into()
+        }
+    }
+
+    #[test]
+    fn stats_summarise_samples() {
+        let samples:Vec<Duration> = [4, 1, 3, 2].iter().map(|millis| Duration::from_millis(*millis)).collect();
+
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.stddev.as_micros(), 1118);
+    }
+
+    #[test]
+    fn parse_and_solve_are_timed_apart() {
+        let input = PuzzleInput { content: "1,2,3".to_string(), path: "example.txt".to_string() };
+
+        let bench = bench_part(&Sum, Part::One, &input, 1, 3).unwrap();
+
+        assert!(bench.parse.min <= bench.total.min);
+        assert!(bench.solve.min <= bench.total.min);
+    }
+
+    #[test]
+    fn parse_errors_stop_the_bench() {
+        let input = PuzzleInput { content: "1,two,3".to_string(), path: "example.txt".to_string() };
+
+        let result = bench_part(&Sum, Part::One, &input, 1, 3);
+
+        assert_eq!(result.err(), Some(ParseError::new("\"two\" is not a number").in_day(1)));
+    }
+
+    #[test]
+    fn baseline_round_trips() {
+        let mut baseline = Baseline::default();
+        let stats = Stats::from_samples(&[Duration::from_nanos(1500)]);
+        baseline.record(&PartBench { day: 17, part: Part::Two, parse: stats, solve: stats, total: stats });
+
+        let parsed = Baseline::parse(&baseline.to_toml()).unwrap();
+
+        assert_eq!(parsed.get(17, Part::Two), Some(Duration::from_nanos(1500)));
+        assert_eq!(parsed.get(17, Part::One), None);
+    }
+
+    #[test]
+    fn can_measure_change_from_baseline() {
+        assert_eq!(change_from(Duration::from_secs(4), Duration::from_secs(5)), 0.25);
+        assert_eq!(change_from(Duration::from_secs(4), Duration::from_secs(2)), -0.5);
+    }
+}
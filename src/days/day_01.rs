@@ -1,14 +1,19 @@
 use std::collections::{BTreeMap, HashMap};
-use std::fmt::Display;
 use itertools;
 use itertools::Itertools;
 use crate::tools::parse_numbers;
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
 
 
-pub fn part_one(input: String) -> impl Display {
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(input.lines().map(|line| line.to_string()).collect())
+}
+
+pub fn part_one(input: &Vec<String>) -> Answer {
     let instructions:Vec<CalibrationInstruction> = input
-        .lines()
-        .into_iter()
+        .iter()
         .map(|f| CalibrationInstruction::new(f))
         .collect();
 
@@ -17,13 +22,12 @@ pub fn part_one(input: String) -> impl Display {
         .map(|instruction| instruction.value)
         .sum::<i32>();
 
-    sum
+    sum.into()
 }
 
-pub fn part_two(input: String) -> impl Display {
+pub fn part_two(input: &Vec<String>) -> Answer {
     let instructions:Vec<CalibrationInstruction> = input
-        .lines()
-        .into_iter()
+        .iter()
         .map(|f| CalibrationInstruction::parse_v2(f))
         .collect();
 
@@ -32,7 +36,33 @@ pub fn part_two(input: String) -> impl Display {
         .map(|instruction| instruction.value)
         .sum::<i32>();
 
-    sum
+    sum.into()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<String>;
+
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Trebuchet?!"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Vec<String>) -> Answer {
+        part_two(input)
+    }
 }
 
 
@@ -1,42 +1,77 @@
 use std::cmp;
-use std::fmt::Display;
 use nom::bytes::complete::{tag, take_until};
 use nom::IResult;
-use crate::tools::parse_numbers;
+use crate::tools::parse_number;
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
 
-pub fn part_one(input: String) -> impl Display {
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    parse_lines(input, Game::new)
+}
+
+pub fn part_one(input: &Vec<Game>) -> Answer {
     let sum = input
-        .lines()
-        .into_iter()
-        .map(|f| Game::new(f).unwrap().1)
+        .iter()
         .filter(|game| game.is_possible(14,12, 13))
         .map(|valid_game| valid_game.id)
         .sum::<i32>();
-    sum
+    sum.into()
 }
 
-pub fn part_two(input: String) -> impl Display {
+pub fn part_two(input: &Vec<Game>) -> Answer {
     let sum = input
-        .lines()
-        .into_iter()
-        .map(|f| Game::new(f).unwrap().1.get_cube_power())
+        .iter()
+        .map(|game| game.get_cube_power())
         .sum::<i32>();
-    sum
+    sum.into()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Game>;
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Cube Conundrum"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Game>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Vec<Game>) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Vec<Game>) -> Answer {
+        part_two(input)
+    }
 }
-struct Game {
-    id: i32,
-    sets: Vec<GameSet>
+pub struct Game {
+    pub id: i32,
+    pub sets: Vec<GameSet>
 }
 
 impl Game {
-    pub fn new(input_line: &str) -> IResult<&str, Self> {
+    pub fn new(line: &str) -> Result<Self, ParseError> {
+        let (sets, id) = Game::parse_id(line).map_err(|error| ParseError::nom(line, error))?;
+
+        let game_sets = sets.split(";").map(|split| GameSet::new(line, split)).collect::<Result<Vec<GameSet>, ParseError>>()?;
+
+        return Ok(Game { id: parse_number(line, id)?, sets: game_sets });
+    }
+
+    fn parse_id(input_line: &str) -> IResult<&str, &str> {
         let (input_line, _) = tag("Game ")(input_line)?;
         let (input_line, id) = take_until(":")(input_line)?;
         let (input_line, _) = tag(": ")(input_line)?;
 
-        let game_sets:Vec<GameSet> = input_line.split(";").map(|split| GameSet::new(split)).collect();
-
-        return Ok((input_line, Game { id: parse_numbers(id).unwrap().1, sets: game_sets }));
+        Ok((input_line, id))
     }
 
     pub fn is_possible(&self, blue_count: i32, red_count: i32, green_count: i32) -> bool {
@@ -65,35 +100,35 @@ impl Game {
 }
 
 #[derive(Debug)]
-struct GameSet {
-    blue: i32,
-    red: i32,
-    green: i32
+pub struct GameSet {
+    pub blue: i32,
+    pub red: i32,
+    pub green: i32
 }
 
 impl GameSet {
-    fn new(input_line: &str) -> Self {
+    pub fn new(line: &str, input_line: &str) -> Result<Self, ParseError> {
         let cube_counts:Vec<&str> = input_line.split(",").collect();
         let mut green_count = 0;
         let mut red_count = 0;
         let mut blue_count = 0;
         for cube_count in cube_counts {
-            if cube_count.contains("green") {
-                green_count = parse_numbers(cube_count.trim()).unwrap().1;
-            }
-            if cube_count.contains("blue") {
-                blue_count = parse_numbers(cube_count.trim()).unwrap().1;
-            }
-            if cube_count.contains("red") {
-                red_count = parse_numbers(cube_count.trim()).unwrap().1;
+            let mut split = cube_count.split_whitespace();
+            let count = split.next().ok_or_else(|| ParseError::at(line, cube_count, "expected a cube count"))?;
+            let colour = split.next().ok_or_else(|| ParseError::at(line, count, "expected a colour after the count"))?;
+            match colour {
+                "green" => green_count = parse_number(line, count)?,
+                "blue" => blue_count = parse_number(line, count)?,
+                "red" => red_count = parse_number(line, count)?,
+                _ => return Err(ParseError::at(line, colour, format!("unknown colour {:?}", colour)))
             }
         }
 
-        return GameSet{
+        return Ok(GameSet{
             blue: blue_count,
             red: red_count,
             green: green_count,
-        }
+        })
     }
 
 }
@@ -107,14 +142,14 @@ mod tests {
         let input = r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"#;
         let game = Game::new(input);
 
-        assert_eq!(game.unwrap().1.sets.iter().count(), 3);
+        assert_eq!(game.unwrap().sets.iter().count(), 3);
         Ok(())
     }
 
     #[test]
     fn game_set_new_can_parse_set_counts() {
         let input = r#"3 blue, 4 red"#;
-        let game_set = GameSet::new(input);
+        let game_set = GameSet::new(input, input).unwrap();
 
         assert_eq!(game_set.blue, 3);
         assert_eq!(game_set.red, 4);
@@ -124,7 +159,7 @@ mod tests {
     #[test]
     fn game_validates_if_is_possible() -> Result<(), String> {
         let input = r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"#;
-        let game = Game::new(input).unwrap().1;
+        let game = Game::new(input).unwrap();
 
         let possible = game.is_possible(14,12, 13);
         assert_eq!(possible, true);
@@ -134,7 +169,7 @@ mod tests {
     #[test]
     fn game_validates_if_impossible() -> Result<(), String> {
         let input = r#"Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"#;
-        let game = Game::new(input).unwrap().1;
+        let game = Game::new(input).unwrap();
 
         let possible = game.is_possible(14,12, 13);
         assert_eq!(possible, false);
@@ -144,10 +179,19 @@ mod tests {
     #[test]
     fn game_returns_power() -> Result<(), String> {
         let input = r#"Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"#;
-        let game = Game::new(input).unwrap().1;
+        let game = Game::new(input).unwrap();
 
         let power = game.get_cube_power();
         assert_eq!(power, 1560);
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn game_points_at_unknown_colour() {
+        let input = r#"Game 2: 1 blue, 2 purple"#;
+        let error = Game::new(input).err().unwrap();
+
+        assert_eq!(error.column, Some(19));
+        assert_eq!(error.message, "unknown colour \"purple\"");
+    }
+}
@@ -1,16 +1,46 @@
 use std::collections::{HashMap, HashSet};
-use std::fmt::Display;
 use crate::domain::point::Point;
-use crate::tools::parse_numbers;
+use crate::tools::parse_number;
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
 
-pub fn part_one(input: String) -> impl Display {
-    let schematic = Schematic::process_schematic(input);
-    schematic.get_part_number_sum()
+pub fn parse(input: &str) -> Result<Schematic, ParseError> {
+    Schematic::process_schematic(input)
 }
 
-pub fn part_two(input: String) -> impl Display {
-    let schematic = Schematic::process_schematic(input);
-    schematic.get_gears().iter().map(|gear| gear.get_gear_power()).sum::<i32>()
+pub fn part_one(input: &Schematic) -> Answer {
+    input.get_part_number_sum().into()
+}
+
+pub fn part_two(input: &Schematic) -> Answer {
+    input.get_gears().iter().map(|gear| gear.get_gear_power()).sum::<i32>().into()
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Schematic;
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Gear Ratios"
+    }
+
+    fn parse(&self, input: &str) -> Result<Schematic, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Schematic) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Schematic) -> Answer {
+        part_two(input)
+    }
 }
 
 struct Gear {
@@ -28,65 +58,56 @@ impl Gear {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
-struct Symbol {
-    indicator: char,
-    position: Point
+pub struct Symbol {
+    pub indicator: char,
+    pub position: Point
 }
 
-struct Schematic {
-    parts: Vec<Part>,
-    symbols: Vec<Symbol>,
+pub struct Schematic {
+    pub parts: Vec<Part>,
+    pub symbols: Vec<Symbol>,
 }
 
 impl Schematic {
-    pub fn process_schematic(input: String) -> Self {
+    pub fn process_schematic(input: &str) -> Result<Self, ParseError> {
         let lines = input.trim().lines();
 
         let mut parts: Vec<Part> = Vec::new();
         let mut symbol_positions: Vec<Symbol> = Vec::new();
-        let mut y_index = 0;
 
-        lines.for_each(|line| {
-            let mut string_buffer = String::from("");
+        for (y_index, line) in lines.enumerate() {
+            let mut number_start = None;
 
-            for i in 0..line.chars().count() {
-                let leading_char = line.chars().nth(i).unwrap();
+            for (i, (offset, leading_char)) in line.char_indices().enumerate() {
                 if leading_char.is_digit(10) {
-                    string_buffer.push(leading_char);
+                    number_start.get_or_insert(offset);
                     continue;
                 }
-                if string_buffer.chars().count() > 0 {
-                    parts.push(Part::new(string_buffer.clone(), i, y_index));
-
-                    string_buffer.clear();
+                if let Some(start) = number_start.take() {
+                    parts.push(Part::new(line, &line[start..offset], i, y_index).map_err(|error| error.on_line(y_index + 1))?);
                 }
 
                 if leading_char != '.' {
                     symbol_positions.push(Symbol { position: Point::parse(i, y_index), indicator: leading_char });
                 }
             }
-            if string_buffer.chars().count() > 0 {
-                parts.push(Part::new(string_buffer.clone(), line.chars().count(), y_index));
-
-                string_buffer.clear();
+            if let Some(start) = number_start.take() {
+                parts.push(Part::new(line, &line[start..], line.chars().count(), y_index).map_err(|error| error.on_line(y_index + 1))?);
             }
+        }
 
-            y_index = y_index + 1;
-        });
-
-
-        Schematic { symbols: symbol_positions, parts }
+        Ok(Schematic { symbols: symbol_positions, parts })
     }
 
 
-    pub fn get_valid_parts(self) -> Vec<Part> {
-        let parts = self.parts.into_iter().filter(|part| {
+    pub fn get_valid_parts(&self) -> Vec<&Part> {
+        let parts = self.parts.iter().filter(|part| {
             part.check_validity(self.symbols.clone())
         }).collect();
         return parts;
     }
 
-    pub fn get_part_number_sum(self) -> i32 {
+    pub fn get_part_number_sum(&self) -> i32 {
         self.get_valid_parts().iter()
             .map(|part| part.number)
             .sum::<i32>()
@@ -130,21 +151,21 @@ impl Schematic {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, PartialOrd, Ord)]
-struct Part {
-    number: i32,
-    positions: Vec<Point>,
+pub struct Part {
+    pub number: i32,
+    pub positions: Vec<Point>,
 }
 
 impl Part {
-    pub fn new(number_value: String, x_position_end: usize, y_position_start: usize) -> Self {
-        let part_number = parse_numbers(&*number_value).unwrap().1;
+    pub fn new(line: &str, number_value: &str, x_position_end: usize, y_position_start: usize) -> Result<Self, ParseError> {
+        let part_number = parse_number(line, number_value)?;
         let string_size = number_value.chars().count();
         let mut positions: Vec<Point> = Vec::new();
         for i in 0..string_size {
             positions.push(Point::parse(x_position_end - i - 1, y_position_start));
         }
 
-        Part { positions, number: part_number }
+        Ok(Part { positions, number: part_number })
     }
 
     pub fn get_possible_symbol_positions(&self) -> Vec<Point> {
@@ -191,7 +212,7 @@ mod tests {
 ...$.*....
 .664*598.."#;
 
-        let schematic = Schematic::process_schematic(input.to_string());
+        let schematic = Schematic::process_schematic(input).unwrap();
         assert_eq!(schematic.parts.iter().count(), 11);
     }
 
@@ -209,7 +230,7 @@ mod tests {
 ...$.*....
 .664.598.."#;
 
-        let schematic = Schematic::process_schematic(input.to_string());
+        let schematic = Schematic::process_schematic(input).unwrap();
         let valid_parts = schematic.get_valid_parts();
         assert_eq!(valid_parts.iter().count(), 8);
     }
@@ -228,7 +249,7 @@ mod tests {
 ...$.*....
 .664*598.."#;
 
-        let schematic = Schematic::process_schematic(input.to_string());
+        let schematic = Schematic::process_schematic(input).unwrap();
         let sum = schematic.get_part_number_sum();
         assert_eq!(sum, 4367);
     }
@@ -247,7 +268,7 @@ mod tests {
 ...$.*....
 .664*598.."#;
 
-        let schematic = Schematic::process_schematic(input.to_string());
+        let schematic = Schematic::process_schematic(input).unwrap();
         let part_map = schematic.build_part_map();
         let part_at = part_map.get(&Point::new(0,0)).unwrap();
         assert_eq!(part_at.clone(), 467);
@@ -267,7 +288,7 @@ mod tests {
 ...$.*....
 .664.598.."#;
 
-        let schematic = Schematic::process_schematic(input.to_string());
+        let schematic = Schematic::process_schematic(input).unwrap();
         let gears = schematic.get_gears();
         assert_eq!(gears.iter().count(), 2);
     }
@@ -286,7 +307,7 @@ mod tests {
 ...$.*....
 .664.598.."#;
 
-        let schematic = Schematic::process_schematic(input.to_string());
+        let schematic = Schematic::process_schematic(input).unwrap();
         let gears = schematic.get_gears();
         assert_eq!(gears.iter().map(|gear| gear.get_gear_power()).sum::<i32>(), 467835);
     }
@@ -1,27 +1,56 @@
 use std::collections::{HashMap, HashSet};
-use std::fmt::Display;
 use itertools::Itertools;
 use nom::bytes::complete::{tag, take_until};
 use nom::IResult;
 use crate::domain::point::Point;
-use crate::tools::{parse_numbers, usize_to_i32, usize_to_u32};
+use crate::tools::{parse_number, usize_to_i32, usize_to_u32};
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
 
-pub fn part_one(input: String) -> impl Display {
-    input.lines().map(|line|
-        Card::parse(line).unwrap().1
-    ).map(|card| card.score_card()).sum::<i32>()
+pub fn parse(input: &str) -> Result<Vec<Card>, ParseError> {
+    parse_lines(input, Card::parse)
 }
 
-pub fn part_two(input: String) -> impl Display {
-    card_counter(input)
+pub fn part_one(input: &Vec<Card>) -> Answer {
+    input.iter().map(|card| card.score_card()).sum::<i32>().into()
 }
 
-fn card_counter(input: String) -> i32 {
+pub fn part_two(input: &Vec<Card>) -> Answer {
+    card_counter(input).into()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Card>;
+
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Scratchcards"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Card>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Vec<Card>) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Vec<Card>) -> Answer {
+        part_two(input)
+    }
+}
+
+pub fn card_counter(cards: &Vec<Card>) -> i32 {
     let mut original_card_map:HashMap<i32, Card> = HashMap::new();
     let mut card_instances:HashMap<i32, Vec<Card>> = HashMap::new();
-    input.lines().for_each(|line|
+    cards.iter().for_each(|card|
         {
-            let card = Card::parse(line).unwrap().1;
             original_card_map.insert(card.id.clone(), card.clone());
             card_instances.insert(card.id.clone(), vec![card.clone()]);
 
@@ -50,24 +79,30 @@ fn card_counter(input: String) -> i32 {
 }
 
 #[derive(Clone)]
-struct Card {
-    winning_numbers: Vec<i32>,
-    numbers_i_have: Vec<i32>,
-    id: i32
+pub struct Card {
+    pub winning_numbers: Vec<i32>,
+    pub numbers_i_have: Vec<i32>,
+    pub id: i32
 }
 impl Card {
 
-    fn parse(input_line: &str) -> IResult<&str, Self> {
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let (_, (id, winning_numbers, numbers_i_have)) = Card::parse_sections(line).map_err(|error| ParseError::nom(line, error))?;
+
+        let my_numbers = numbers_i_have.split_whitespace().map(|item| parse_number(line, item)).collect::<Result<_, _>>()?;
+        let winning = winning_numbers.split_whitespace().map(|item| parse_number(line, item)).collect::<Result<_, _>>()?;
+
+        Ok(Card { numbers_i_have: my_numbers, winning_numbers: winning, id: parse_number(line, id)? })
+    }
+
+    fn parse_sections(input_line: &str) -> IResult<&str, (&str, &str, &str)> {
         let (input_line, _) = tag("Card ")(input_line)?;
         let (input_line, id) = take_until(": ")(input_line)?;
         let (input_line, _) = tag(":")(input_line)?;
         let (input_line, winning_numbers) = take_until("| ")(input_line)?;
         let (numbers_i_have, _) = tag("|")(input_line)?;
 
-        let my_numbers = numbers_i_have.split_whitespace().map(|item| parse_numbers(item).unwrap().1).collect();
-        let winning = winning_numbers.split_whitespace().map(|item| parse_numbers(item).unwrap().1).collect();
-
-        Ok((input_line, Card { numbers_i_have: my_numbers, winning_numbers: winning, id: parse_numbers(id.trim()).unwrap().1 }))
+        Ok((input_line, (id, winning_numbers, numbers_i_have)))
     }
 
     fn score_card(&self) -> i32 {
@@ -103,12 +138,13 @@ mod tests {
     use nom::IResult;
     use super::Card;
     use super::card_counter;
+    use super::parse;
 
     #[test]
     fn card_can_be_parsed() -> Result<(), String> {
         let input_line = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"#;
 
-        let card = Card::parse(input_line).unwrap().1;
+        let card = Card::parse(input_line).unwrap();
 
         assert_eq!(card.winning_numbers.iter().count(), 5);
         Ok(())
@@ -118,7 +154,7 @@ mod tests {
     fn card_can_be_scored() -> Result<(), String> {
         let input_line = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"#;
 
-        let card = Card::parse(input_line).unwrap().1;
+        let card = Card::parse(input_line).unwrap();
 
         assert_eq!(card.score_card(), 8);
         Ok(())
@@ -128,7 +164,7 @@ mod tests {
     fn non_winning_card_score_is_zero() -> Result<(), String> {
         let input_line = r#"Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36"#;
 
-        let card = Card::parse(input_line).unwrap().1;
+        let card = Card::parse(input_line).unwrap();
 
         assert_eq!(card.score_card(), 0);
         Ok(())
@@ -138,7 +174,7 @@ mod tests {
     fn card_with_one_winner_returns_one() -> Result<(), String> {
         let input_line = r#"Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83"#;
 
-        let card = Card::parse(input_line).unwrap().1;
+        let card = Card::parse(input_line).unwrap();
 
         assert_eq!(card.score_card(), 1);
         Ok(())
@@ -153,7 +189,7 @@ Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
 
-        let result = card_counter(input.to_string());
+        let result = card_counter(&parse(input).unwrap());
 
         assert_eq!(result, 30);
     }
@@ -5,22 +5,59 @@ use std::str::FromStr;
 use itertools::{Itertools, min};
 use nom::bytes::complete::{tag, take_until};
 use nom::IResult;
-use crate::tools::{parse_numbers, parse_numbers_u64};
+use crate::tools::{array_chunks, parse_number};
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
 
-pub fn part_one(input: String) -> impl Display {
-    map_seeds_to_location(input)
+pub fn parse(input: &str) -> Result<Almanac, ParseError> {
+    let mut split = input.split("\n\n");
+    let seeds = Seeds::parse(split.nth(0).unwrap_or_default()).map_err(|error| error.on_line(1))?;
+    let maps = split.map(|section| Map::new(section).map_err(|error| error.within(input, section))).collect::<Result<_, _>>()?;
+
+    Ok(Almanac { seeds, maps })
 }
 
-fn map_seeds_to_location(input: String) -> u64 {
-    let mut split = input.split("\n\n");
-    let seeds = Seeds::parse(split.nth(0).unwrap()).unwrap().1;
-    let maps:Vec<Map> = split.map(|section| Map::new(section)).collect();
+pub fn part_one(input: &Almanac) -> Answer {
+    map_seeds_to_location(input).into()
+}
+
+pub fn part_two(input: &Almanac) -> Answer {
+    map_seeds_to_location_with_seed_range_v2_final(input).into()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Almanac;
+
+    fn day(&self) -> u32 {
+        5
+    }
 
+    fn title(&self) -> &'static str {
+        "If You Give A Seed A Fertilizer"
+    }
+
+    fn parse(&self, input: &str) -> Result<Almanac, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Almanac) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Almanac) -> Answer {
+        part_two(input)
+    }
+}
+
+pub fn map_seeds_to_location(almanac: &Almanac) -> u64 {
     let mut locations:Vec<u64> = Vec::new();
 
-    seeds.seeds.iter().for_each(|seed_start| {
+    almanac.seeds.seeds.iter().for_each(|seed_start| {
         let mut tracking_value = seed_start.clone();
-        maps.iter().for_each(|map| {
+        almanac.maps.iter().for_each(|map| {
             tracking_value = map.map_input(tracking_value);
         });
         locations.push(tracking_value);
@@ -34,23 +71,14 @@ fn map_seeds_to_location(input: String) -> u64 {
 }
 
 
-pub fn part_two(input: String) -> impl Display {
-    map_seeds_to_location_with_seed_range_v2_final(input)
-}
-
-
-fn map_seeds_to_location_with_seed_range(input: String) -> u64 {
-    let mut split = input.split("\n\n");
-    let seeds = Seeds::parse(split.nth(0).unwrap()).unwrap().1;
-    let maps:Vec<Map> = split.map(|section| Map::new(section)).collect();
-
+pub fn map_seeds_to_location_with_seed_range(almanac: &Almanac) -> u64 {
     let mut locations:Vec<(u64,u64)> = Vec::new();
 
-    seeds.seed_ranges.iter().for_each(|seed_start| {
+    almanac.seeds.seed_ranges.iter().for_each(|seed_start| {
         println!("Starting seed range {}", seed_start.start);
         for seed_value in (seed_start.start..seed_start.start + seed_start.range).step_by(25) {
             let mut tracking_value = seed_value.clone();
-            maps.iter().for_each(|map| {
+            almanac.maps.iter().for_each(|map| {
                 tracking_value = map.map_input(tracking_value);
             });
             locations.push((seed_start.start, tracking_value));
@@ -64,30 +92,22 @@ fn map_seeds_to_location_with_seed_range(input: String) -> u64 {
 }
 
 
-fn map_seeds_to_location_with_seed_range_v2_final(input: String) -> u64 {
-    let mut split = input.split("\n\n");
-    let seeds = Seeds::parse(split.nth(0).unwrap()).unwrap().1;
-    let maps:Vec<Map> = split.map(|section| Map::new(section)).collect();
-    let seed_ranges:Vec<Range> = seeds.seed_ranges.iter().map(|seed_range| Range::new(seed_range.start, seed_range.range)).collect();
+pub fn map_seeds_to_location_with_seed_range_v2_final(almanac: &Almanac) -> u64 {
+    let seed_ranges:Vec<Range> = almanac.seeds.seed_ranges.iter().map(|seed_range| Range::new(seed_range.start, seed_range.range)).collect();
 
     let mut lowest:u64 = u64::MAX;
     seed_ranges.iter().for_each(|range| {
-        println!("RANGE {} {}", range.intervals[0].start, range.intervals[0].length);
         let seed_start = range.intervals[0].start;
         let mut end_range = range.clone();
-        maps.iter().for_each(|map| {
+        almanac.maps.iter().for_each(|map| {
             end_range = map.apply_range_mapping(end_range.clone());
         });
 
         let final_mapping = end_range.get_final_mapping_ranges();
         final_mapping.iter().for_each(|kvp| {
-            println!("{} - {}", kvp.0, kvp.1 + seed_start);
             lowest = std::cmp::min(lowest, *kvp.0);
         });
-        println!("{}", end_range.intervals.iter().count());
     });
-    println!("{}", lowest);
-
     lowest
 }
 
@@ -126,29 +146,40 @@ pub enum MapType {
 }
 
 
-struct Map {
-    map_entries: Vec<MapEntry>,
-    source_entity: EntityType,
-    destination_entity: EntityType
+pub struct Map {
+    pub map_entries: Vec<MapEntry>,
+    pub source_entity: EntityType,
+    pub destination_entity: EntityType
 }
 
 impl Map {
-    fn new(input_block: &str) -> Self {
-        let first_line = input_block.lines().nth(0).unwrap();
-        let map_type = Map::get_map_type(first_line).unwrap().1;
+    pub fn new(input_block: &str) -> Result<Self, ParseError> {
+        let first_line = input_block.lines().nth(0).unwrap_or_default();
+        let map_type = Map::get_map_type(first_line).map_err(|error| error.on_line(1))?;
 
-        let map_entries:Vec<MapEntry> = input_block.lines().skip(1).map(|line| MapEntry::from_str(line).unwrap()).collect();
+        let map_entries:Vec<MapEntry> = input_block.lines().skip(1)
+            .enumerate()
+            .map(|(index, line)| MapEntry::from_str(line).map_err(|error| error.on_line(index + 2)))
+            .collect::<Result<_, _>>()?;
 
 
-        Map { source_entity: map_type.0, destination_entity: map_type.1, map_entries }
+        Ok(Map { source_entity: map_type.0, destination_entity: map_type.1, map_entries })
     }
 
-    fn get_map_type(input_line: &str) -> IResult<&str, (EntityType, EntityType)> {
+    fn get_map_type(input_line: &str) -> Result<(EntityType, EntityType), ParseError> {
+        let (_, (source_entity, destination_entity)) = Map::split_map_type(input_line).map_err(|error| ParseError::nom(input_line, error))?;
+        let entity = |token: &str| EntityType::from_str(token)
+            .map_err(|_| ParseError::at(input_line, token, format!("unknown entity {:?}", token)));
+
+        Ok((entity(source_entity)?, entity(destination_entity)?))
+    }
+
+    fn split_map_type(input_line: &str) -> IResult<&str, (&str, &str)> {
         let (input_line, source_entity) = take_until("-")(input_line)?;
         let (input_line, tag) = tag("-to-")(input_line)?;
         let (input_line, destination_entity) = take_until(" ")(input_line)?;
 
-        Ok((input_line, (EntityType::from_str(source_entity).unwrap(), EntityType::from_str(destination_entity).unwrap())))
+        Ok((input_line, (source_entity, destination_entity)))
     }
 
     fn map_input(&self, entity_id: u64) -> u64 {
@@ -202,10 +233,10 @@ impl Range {
 }
 
 
-struct MapEntry {
-    destination_start: u64,
-    source_start: u64,
-    range: u64
+pub struct MapEntry {
+    pub destination_start: u64,
+    pub source_start: u64,
+    pub range: u64
 }
 
 impl MapEntry {
@@ -242,44 +273,55 @@ impl MapEntry {
     }
 }
 impl FromStr for MapEntry {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
 
         let mut split = s.split_whitespace();
-        let dest = parse_numbers_u64(split.nth(0).unwrap()).unwrap().1;
-        let source = parse_numbers_u64(split.nth(0).unwrap()).unwrap().1;
-        let range = parse_numbers_u64(split.nth(0).unwrap()).unwrap().1;
+        let mut next_number = |expected: &str| match split.next() {
+            Some(token) => parse_number::<u64>(s, token),
+            None => Err(ParseError::missing(s, expected))
+        };
+        let dest = next_number("a destination start")?;
+        let source = next_number("a source start")?;
+        let range = next_number("a range length")?;
 
         Ok(MapEntry { destination_start: dest, source_start: source, range })
     }
 }
 
-struct Seed {
-    start: u64,
-    range: u64
+/// The seeds to plant and the chain of maps from seed through to location.
+pub struct Almanac {
+    pub seeds: Seeds,
+    pub maps: Vec<Map>
+}
+
+pub struct Seed {
+    pub start: u64,
+    pub range: u64
 }
 
 
-struct Seeds {
-    seeds: Vec<u64>,
-    seed_ranges: Vec<Seed>
+pub struct Seeds {
+    pub seeds: Vec<u64>,
+    pub seed_ranges: Vec<Seed>
 }
 impl Seeds {
-    fn parse(input_line: &str) -> IResult<&str, Self> {
-        let (input_line, _) = tag("seeds: ")(input_line)?;
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let (input_line, _) = tag::<_, _, nom::error::Error<&str>>("seeds: ")(line).map_err(|error| ParseError::nom(line, error))?;
         let seeds = input_line
             .split_whitespace()
-            .map(|value| parse_numbers_u64(value).unwrap().1)
-            .collect();
+            .map(|value| parse_number(line, value))
+            .collect::<Result<_, _>>()?;
 
-        let seed_ranges = input_line.split_whitespace().collect::<Vec<&str>>().chunks(2).map(|chunk| {
-            let start = parse_numbers_u64(chunk[0]).unwrap().1;
-            let range = parse_numbers_u64(chunk[1]).unwrap().1;
-            return Seed { start, range }
-        }).collect();
+        if input_line.split_whitespace().count() % 2 == 1 {
+            return Err(ParseError::missing(line, "a range length after the last seed"));
+        }
+        let seed_ranges = array_chunks(input_line.split_whitespace()).map(|[start, range]| {
+            return Ok(Seed { start: parse_number(line, start)?, range: parse_number(line, range)? })
+        }).collect::<Result<_, ParseError>>()?;
 
-        Ok((input_line, Seeds { seeds, seed_ranges }))
+        Ok(Seeds { seeds, seed_ranges })
     }
 }
 
@@ -287,7 +329,7 @@ impl Seeds {
 #[cfg(test)]
 mod tests {
     use crate::days::day_05::EntityType::{Seed, Soil};
-    use super::{Map, map_seeds_to_location, map_seeds_to_location_with_seed_range, map_seeds_to_location_with_seed_range_v2_final, MapEntry, Range};
+    use super::{Map, map_seeds_to_location, parse, map_seeds_to_location_with_seed_range, map_seeds_to_location_with_seed_range_v2_final, MapEntry, Range};
     #[test]
     fn mapping_can_get_locations() {
         let input = r#"seeds: 79 14 55 13
@@ -324,7 +366,7 @@ humidity-to-location map:
 60 56 37
 56 93 4"#.to_string();
 
-        let result = map_seeds_to_location(input);
+        let result = map_seeds_to_location(&parse(&input).unwrap());
 
         assert_eq!(result, 35);
     }
@@ -365,7 +407,7 @@ humidity-to-location map:
 60 56 37
 56 93 4"#.to_string();
 
-        let result = map_seeds_to_location_with_seed_range(input);
+        let result = map_seeds_to_location_with_seed_range(&parse(&input).unwrap());
 
         assert_eq!(result, 46);
     }
@@ -406,7 +448,7 @@ humidity-to-location map:
 60 56 37
 56 93 4"#.to_string();
 
-        let result = map_seeds_to_location_with_seed_range_v2_final(input);
+        let result = map_seeds_to_location_with_seed_range_v2_final(&parse(&input).unwrap());
 
         assert_eq!(result, 46);
     }
@@ -432,4 +474,15 @@ humidity-to-location map:
         assert_eq!(cut_range.intervals[1].start, 100);
     }
 
+
+    #[test]
+    fn parse_errors_carry_line_within_file() {
+        let input = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50\n";
+
+        let error = parse(input).err().unwrap();
+
+        assert_eq!(error.line, Some(5));
+        assert_eq!(error.column, Some(6));
+        assert_eq!(error.message, "expected a range length");
+    }
 }
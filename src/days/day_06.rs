@@ -1,22 +1,63 @@
-use std::fmt::Display;
 use num::traits::real::Real;
-use crate::tools::{parse_numbers_i64, usize_to_i64};
+use crate::tools::{parse_number, parse_numbers_i64, usize_to_i64};
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
 
-pub fn part_one(input: String) -> impl Display {
-    parse_number_of_winning_races(input)
+pub fn parse(input: &str) -> Result<RaceSheet, ParseError> {
+    let mut lines = input.lines();
+    let times = parse_values("Time:", lines.next().unwrap_or_default()).map_err(|error| error.on_line(1))?;
+    let distance_line = lines.next().unwrap_or_default();
+    let distances = parse_values("Distance:", distance_line).map_err(|error| error.on_line(2))?;
+
+    if times.len() != distances.len() {
+        return Err(ParseError::at(distance_line, distance_line, format!("{} times but {} distances", times.len(), distances.len())).on_line(2));
+    }
+
+    Ok(RaceSheet { times, distances })
+}
+
+pub fn part_one(input: &RaceSheet) -> Answer {
+    parse_number_of_winning_races(input).into()
 }
 
-pub fn part_two(input: String) -> impl Display {
-    parse_mega_race_winning(input)
+pub fn part_two(input: &RaceSheet) -> Answer {
+    parse_mega_race_winning(input).into()
 }
 
-fn parse_number_of_winning_races(input: String) -> i64 {
-    let races = parse_races(input);
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = RaceSheet;
+
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Wait For It"
+    }
+
+    fn parse(&self, input: &str) -> Result<RaceSheet, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &RaceSheet) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &RaceSheet) -> Answer {
+        part_two(input)
+    }
+}
+
+pub fn parse_number_of_winning_races(sheet: &RaceSheet) -> i64 {
+    let races = parse_races(sheet);
     determine_race_winning_margin_of_error(races)
 }
 
-fn parse_mega_race_winning(input: String) -> i64 {
-    let races = vec![parse_mega_race(&input)];
+pub fn parse_mega_race_winning(sheet: &RaceSheet) -> i64 {
+    let races = vec![parse_mega_race(sheet)];
     determine_race_winning_margin_of_error(races)
 }
 
@@ -35,47 +76,39 @@ fn determine_race_winning_margin_of_error(races: Vec<Race>) -> i64 {
         return usize_to_i64(winning_races).unwrap()
     }).collect();
 
-    winning_races.iter().fold(1, |acc, num| {
-        println!("{}", num);
-        acc * num
-    })
+    winning_races.iter().fold(1, |acc, num| acc * num)
 }
-fn parse_races(input: String) -> Vec<Race> {
-    let mut lines = input.lines();
-    let times:Vec<i64> = parse_values("Time:", lines.nth(0).unwrap());
-    let distance:Vec<i64> = parse_values("Distance:", lines.nth(0).unwrap());
+fn parse_races(sheet: &RaceSheet) -> Vec<Race> {
     let mut races:Vec<Race> = Vec::new();
 
-    for i in 0..times.iter().count() {
-        races.push(Race::new(times[i], distance[i]));
+    for i in 0..sheet.times.iter().count() {
+        races.push(Race::new(sheet.times[i], sheet.distances[i]));
     }
 
     races
 }
 
-fn parse_mega_race(input: &str) -> Race {
-    let mut lines = input.lines();
-    let time_str :String = lines.nth(0)
-        .unwrap()
-        .replace("Time:", "")
-        .split_whitespace()
-        .collect();
+// The sheet was badly kerned, the columns are really the digits of one long race
+fn parse_mega_race(sheet: &RaceSheet) -> Race {
+    let time_str :String = sheet.times.iter().map(|time| time.to_string()).collect();
     let time = parse_numbers_i64(&time_str).unwrap().1;
-    let distance_str :String = lines.nth(0)
-        .unwrap()
-        .replace("Distance:", "")
-        .split_whitespace()
-        .collect();
+    let distance_str :String = sheet.distances.iter().map(|distance| distance.to_string()).collect();
     let distance = parse_numbers_i64(&distance_str).unwrap().1;
 
     Race::new(time, distance)
 }
 
 
-fn parse_values(value_type: &str, input_line: &str) -> Vec<i64> {
-    let input_line = input_line.replace(value_type, "");
-    input_line.split_whitespace().map(|time_entry| parse_numbers_i64(time_entry).unwrap().1).collect()
+fn parse_values(value_type: &str, line: &str) -> Result<Vec<i64>, ParseError> {
+    let input_line = line.strip_prefix(value_type).ok_or_else(|| ParseError::at(line, line, format!("expected a line starting with {:?}", value_type)))?;
+    input_line.split_whitespace().map(|time_entry| parse_number(line, time_entry)).collect()
 }
+/// The race times and record distances as written on the sheet.
+pub struct RaceSheet {
+    pub times: Vec<i64>,
+    pub distances: Vec<i64>
+}
+
 struct Race {
     time: i64,
     record_distance: i64,
@@ -110,14 +143,14 @@ impl RaceConfiguration {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day_06::{parse_mega_race_winning, parse_number_of_winning_races};
+    use crate::days::day_06::{parse, parse_mega_race_winning, parse_number_of_winning_races};
 
     #[test]
     fn can_parse_race() {
         let input = r#"Time:      7  15   30
 Distance:  9  40  200"#;
 
-        let result = parse_number_of_winning_races(input.to_string());
+        let result = parse_number_of_winning_races(&parse(input).unwrap());
 
         assert_eq!(result, 288);
     }
@@ -127,7 +160,7 @@ Distance:  9  40  200"#;
         let input = r#"Time:      7  15   30
 Distance:  9  40  200"#;
 
-        let result = parse_mega_race_winning(input.to_string());
+        let result = parse_mega_race_winning(&parse(input).unwrap());
 
         assert_eq!(result, 71503);
     }
@@ -2,23 +2,56 @@ use std::cmp::Ordering;
 use std::fmt::{Display, Formatter, write};
 use std::ops::Index;
 use itertools::Itertools;
-use crate::tools::{parse_numbers_u64, usize_to_u32, usize_to_u64};
+use crate::tools::{parse_number, usize_to_u32, usize_to_u64};
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
 
 
 const CARDS: [char; 13] = ['A', 'K', 'Q', 'T', '9', '8', '7', '6', '5', '4', '3', '2', 'J'];
 
-pub fn part_one(input: String) -> impl Display {
-    let hands:Vec<Hand> = input.lines().map(|line| Hand::parse(line)).sorted().collect();
+pub fn parse(input: &str) -> Result<Vec<HandBid>, ParseError> {
+    parse_lines(input, HandBid::parse)
+}
+
+pub fn part_one(input: &Vec<HandBid>) -> Answer {
+    let hands:Vec<Hand> = input.iter().map(|bid| Hand::new(bid)).sorted().collect();
     //bidder(hands)
-    0
+    0.into()
 }
 
-pub fn part_two(input: String) -> impl Display {
-    let hands:Vec<Hand> = input.lines().map(|line| Hand::parse_v2(line)).sorted().collect();
-    bidder(hands)
+pub fn part_two(input: &Vec<HandBid>) -> Answer {
+    let hands:Vec<Hand> = input.iter().map(|bid| Hand::new_v2(bid)).sorted().collect();
+    bidder(hands).into()
 }
 
-fn bidder(hands:Vec<Hand>) -> u64 {
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<HandBid>;
+
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Camel Cards"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<HandBid>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Vec<HandBid>) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Vec<HandBid>) -> Answer {
+        part_two(input)
+    }
+}
+
+pub fn bidder(hands:Vec<Hand>) -> u64 {
     let mut running_total:u64 = 0;
     for i in 0..hands.iter().count() {
         let hand = hands.get(i).unwrap();
@@ -30,7 +63,7 @@ fn bidder(hands:Vec<Hand>) -> u64 {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
-enum HandType {
+pub enum HandType {
     FiveOfAKind = 6,
     FourOfAKind = 5,
     FullHouse = 4,
@@ -54,29 +87,52 @@ impl Display for HandType {
     }
 }
 
-struct Hand {
+/// A line of the input, the cards as dealt and what was bid on them.
+pub struct HandBid {
+    pub cards: Vec<char>,
+    pub score: u64
+}
+
+impl HandBid {
+    pub fn parse(input_line: &str) -> Result<Self, ParseError> {
+        let mut split = input_line.split_whitespace();
+        let hand = split.next().ok_or_else(|| ParseError::missing(input_line, "a hand of cards"))?;
+        if let Some((offset, card)) = hand.char_indices().find(|(_, card)| !CARDS.contains(card)) {
+            return Err(ParseError::at(input_line, &hand[offset..], format!("unknown card {:?}", card)));
+        }
+        if hand.chars().count() != 5 {
+            return Err(ParseError::at(input_line, hand, format!("expected five cards, found {}", hand.chars().count())));
+        }
+        let cards:Vec<char> = hand.chars().collect();
+        let score = parse_number(input_line, split.next().ok_or_else(|| ParseError::missing(input_line, "a bid"))?)?;
+
+        Ok(HandBid { cards, score })
+    }
+}
+
+pub struct Hand {
     cards: Vec<char>,
     hand_type: HandType,
     score: u64
 }
 
 impl Hand {
-    fn parse(input_line: &str) -> Self {
-        let mut split = input_line.split_whitespace();
-        let hand_str:Vec<char> = split.nth(0).unwrap().chars().collect();
-        let hand_type = Hand::determine_hand_type(hand_str.clone());
-        let score = parse_numbers_u64(split.nth(0).unwrap()).unwrap().1;
+    fn new(bid: &HandBid) -> Self {
+        let hand_type = Hand::determine_hand_type(bid.cards.clone());
+        Hand { cards: bid.cards.clone(), hand_type, score: bid.score }
+    }
 
-        Hand { cards: hand_str, hand_type, score }
+    fn new_v2(bid: &HandBid) -> Self {
+        let hand_type = Hand::determine_hand_type_v2(bid.cards.clone());
+        Hand { cards: bid.cards.clone(), hand_type, score: bid.score }
     }
 
-    fn parse_v2(input_line: &str) -> Self {
-        let mut split = input_line.split_whitespace();
-        let hand_str:Vec<char> = split.nth(0).unwrap().chars().collect();
-        let hand_type = Hand::determine_hand_type_v2(hand_str.clone());
-        let score = parse_numbers_u64(split.nth(0).unwrap()).unwrap().1;
+    pub fn parse(input_line: &str) -> Result<Self, ParseError> {
+        Ok(Hand::new(&HandBid::parse(input_line)?))
+    }
 
-        Hand { cards: hand_str, hand_type, score }
+    pub fn parse_v2(input_line: &str) -> Result<Self, ParseError> {
+        Ok(Hand::new_v2(&HandBid::parse(input_line)?))
     }
 
     fn determine_hand_type_v2(hand: Vec<char>) -> HandType {
@@ -180,7 +236,6 @@ impl Ord for Hand {
                 return CARDS.iter().position(|&c| c == other.cards[i]).unwrap().cmp(&CARDS.iter().position(|&c| c == self.cards[i]).unwrap())
             }
         }
-        println!("help");
         self.score.cmp(&other.score)
     }
 
@@ -196,7 +251,7 @@ mod tests {
     fn hand_can_be_parsed_from_string() {
         let input = r#"32T3K 765"#;
 
-        let hand = Hand::parse(input);
+        let hand = Hand::parse(input).unwrap();
 
         assert_eq!(hand.hand_type, HandType::OnePair);
         assert_eq!(hand.score, 765);
@@ -206,7 +261,7 @@ mod tests {
     fn two_pair_hand_can_be_parsed_from_string() {
         let input = r#"KTJJT 220"#;
 
-        let hand = Hand::parse(input);
+        let hand = Hand::parse(input).unwrap();
 
         assert_eq!(hand.hand_type, HandType::TwoPair);
         assert_eq!(hand.score, 220);
@@ -220,7 +275,7 @@ KK677 28
 KTJJT 220
 QQQJA 483"#;
 
-        let hands:Vec<Hand> = input.lines().map(|line| Hand::parse(line)).sorted().collect();
+        let hands:Vec<Hand> = input.lines().map(|line| Hand::parse(line).unwrap()).sorted().collect();
 
         assert_eq!(hands[0].cards, vec!['3','2','T','3','K']);
         assert_eq!(hands[4].cards, vec!['Q','Q','Q','J','A']);
@@ -235,7 +290,7 @@ KK677 28
 KTJJT 220
 QQQJA 483"#;
 
-        let hands:Vec<Hand> = input.lines().map(|line| Hand::parse(line)).sorted().collect();
+        let hands:Vec<Hand> = input.lines().map(|line| Hand::parse(line).unwrap()).sorted().collect();
         let total = bidder(hands);
 
         assert_eq!(total, 6440);
@@ -245,7 +300,7 @@ QQQJA 483"#;
     fn jokers_can_be_processed() {
         let input = r#"T55J5 684"#;
 
-        let hand = Hand::parse_v2(input);
+        let hand = Hand::parse_v2(input).unwrap();
 
         assert_eq!(hand.hand_type, HandType::FourOfAKind);
         assert_eq!(hand.score, 684);
@@ -259,7 +314,7 @@ KK677 28
 KTJJT 220
 QQQJA 483"#;
 
-        let hands:Vec<Hand> = input.lines().map(|line| Hand::parse_v2(line)).sorted().collect();
+        let hands:Vec<Hand> = input.lines().map(|line| Hand::parse_v2(line).unwrap()).sorted().collect();
         let total = bidder(hands);
 
         assert_eq!(total, 5905);
@@ -267,8 +322,8 @@ QQQJA 483"#;
 
     #[test]
     fn joker_hands_can_be_sorted() {
-        let hand_1 = Hand::parse_v2(r#"KTJJT 220"#);
-        let hand_2 = Hand::parse_v2(r#"QQQJA 483"#);
+        let hand_1 = Hand::parse_v2(r#"KTJJT 220"#).unwrap();
+        let hand_2 = Hand::parse_v2(r#"QQQJA 483"#).unwrap();
 
         let ordering = hand_1.cmp(&hand_2);
         assert_eq!(ordering.is_gt(), true);
@@ -276,22 +331,30 @@ QQQJA 483"#;
 
     #[test]
     fn all_joker_hand_is_processed() {
-        let hand_1 = Hand::parse_v2(r#"JJJJJ 287"#);
+        let hand_1 = Hand::parse_v2(r#"JJJJJ 287"#).unwrap();
 
         assert_eq!(hand_1.hand_type, HandType::FiveOfAKind);
     }
 
     #[test]
     fn full_houses_are_parsed() {
-        let hand_1 = Hand::parse_v2(r#"JKKQQ 100"#);
+        let hand_1 = Hand::parse_v2(r#"JKKQQ 100"#).unwrap();
 
         assert_eq!(hand_1.hand_type, HandType::FullHouse);
     }
 
     #[test]
     fn jokers_are_not_reused() {
-        let hand_1 = Hand::parse_v2(r#"JKKTQ 100"#);
+        let hand_1 = Hand::parse_v2(r#"JKKTQ 100"#).unwrap();
 
         assert_eq!(hand_1.hand_type, HandType::ThreeOfAKind);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn unknown_cards_are_reported() {
+        let error = Hand::parse("32X3K 765").err().unwrap();
+
+        assert_eq!(error.column, Some(3));
+        assert_eq!(error.message, "unknown card 'X'");
+    }
+}
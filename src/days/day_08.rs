@@ -1,26 +1,65 @@
 use std::collections::HashMap;
-use std::fmt::Display;
 use itertools::Itertools;
 use nom::bytes::complete::{tag, take, take_until};
 use nom::IResult;
 use num::integer::gcd;
 use crate::days::day_08::Direction::{Left, Right};
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
 
-pub fn part_one(input: String) -> impl Display {
-    find_steps_to_end(input)
+pub fn parse(input: &str) -> Result<Network, ParseError> {
+    let mut lines = input.lines();
+    let instructions = parse_instructions(lines.nth(0).unwrap_or_default()).map_err(|error| error.on_line(1))?;
+    lines.nth(0);
+
+    // Nodes start after the instructions and a blank line
+    let nodes = lines
+        .enumerate()
+        .map(|(index, line)| Node::parse(line)
+            .map(|(_, node)| (node.id, (node.left, node.right)))
+            .map_err(|error| ParseError::nom(line, error).on_line(index + 3)))
+        .collect::<Result<HashMap<String, (String, String)>, ParseError>>()?;
+
+    Ok(Network { instructions, nodes })
 }
 
-pub fn part_two(input: String) -> impl Display {
-    find_steps_to_end_part_2(input)
+pub fn part_one(input: &Network) -> Answer {
+    find_steps_to_end(input).into()
 }
 
-fn find_steps_to_end(input: String) -> usize {
-    let mut lines = input.lines();
-    let mut instructions = parse_instructions(lines.nth(0).unwrap());
-    lines.nth(0).unwrap();
+pub fn part_two(input: &Network) -> Answer {
+    find_steps_to_end_part_2(input).into()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Network;
+
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Haunted Wasteland"
+    }
+
+    fn parse(&self, input: &str) -> Result<Network, ParseError> {
+        parse(input)
+    }
 
-    let mut node_map:HashMap<String, (String, String)> = HashMap::new();
-    node_map = lines.map(|line| Node::parse(line).unwrap().1).map(|x| (x.id, (x.left.clone(), x.right.clone()))).collect();
+    fn part_one(&self, input: &Network) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Network) -> Answer {
+        part_two(input)
+    }
+}
+
+pub fn find_steps_to_end(network: &Network) -> usize {
+    let (instructions, node_map) = (&network.instructions, &network.nodes);
 
     let instruction_size = instructions.iter().count();
     let mut current_node_key = "AAA";
@@ -42,13 +81,8 @@ fn find_steps_to_end(input: String) -> usize {
     number_of_steps
 }
 
-fn find_steps_to_end_part_2(input: String) -> usize {
-    let mut lines = input.lines();
-    let mut instructions = parse_instructions(lines.nth(0).unwrap());
-    lines.nth(0).unwrap();
-
-    let mut node_map:HashMap<String, (String, String)> = HashMap::new();
-    node_map = lines.map(|line| Node::parse(line).unwrap().1).map(|x| (x.id, (x.left.clone(), x.right.clone()))).collect();
+pub fn find_steps_to_end_part_2(network: &Network) -> usize {
+    let (instructions, node_map) = (&network.instructions, &network.nodes);
 
     let instruction_size = instructions.iter().count();
     let mut current_nodes:Vec<&str> = node_map.keys().into_iter().filter(|key| key.ends_with("A")).map(|key| &**key).collect();
@@ -107,21 +141,31 @@ fn find_lcm_of_set(numbers: Vec<usize>) -> usize {
 }
 
 
-fn parse_instructions(input_line: &str) -> Vec<Direction> {
-    input_line.chars().map(|char| {
+fn parse_instructions(input_line: &str) -> Result<Vec<Direction>, ParseError> {
+    if input_line.is_empty() {
+        return Err(ParseError::missing(input_line, "left and right instructions"));
+    }
+
+    input_line.char_indices().map(|(offset, char)| {
         match char {
-            'L' => Left,
-            'R' => Right,
-            _ => panic!("ah")
+            'L' => Ok(Left),
+            'R' => Ok(Right),
+            _ => Err(ParseError::at(input_line, &input_line[offset..], format!("expected L or R, found {:?}", char)))
         }
     }).collect()
 }
 
-enum Direction {
+pub enum Direction {
     Left,
     Right
 }
 
+/// The left/right instructions and where each node leads.
+pub struct Network {
+    pub instructions: Vec<Direction>,
+    pub nodes: HashMap<String, (String, String)>
+}
+
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, PartialOrd, Ord)]
 struct Node {
@@ -146,7 +190,7 @@ impl Node {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day_08::find_steps_to_end_part_2;
+    use crate::days::day_08::{find_steps_to_end_part_2, parse};
 
     #[test]
     fn can_path_find_with_ghost_logic() {
@@ -161,7 +205,7 @@ mod tests {
 22Z = (22B, 22B)
 XXX = (XXX, XXX)"#;
 
-        let result = find_steps_to_end_part_2(input.to_string());
+        let result = find_steps_to_end_part_2(&parse(input).unwrap());
 
         assert_eq!(result, 6);
     }
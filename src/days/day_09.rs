@@ -1,33 +1,66 @@
-use std::fmt::Display;
 use itertools::Itertools;
 use nom::sequence::pair;
-use crate::tools::parse_numbers_i64;
+use crate::tools::parse_number;
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
 
-pub fn part_one(input: String) -> impl Display {
-    input.lines().map(|line| History::parse(line))
+pub fn parse(input: &str) -> Result<Vec<History>, ParseError> {
+    parse_lines(input, History::parse)
+}
+
+pub fn part_one(input: &Vec<History>) -> Answer {
+    input.iter()
         .map(|history| {
-            return history.process_until_end()
+            return history.clone().process_until_end()
                 .predict_sequences_next_value()
                 .get_prediction_value()
-        }).sum::<i64>()
+        }).sum::<i64>().into()
 }
 
-pub fn part_two(input: String) -> impl Display {
-    input.lines().map(|line| History::parse(line))
+pub fn part_two(input: &Vec<History>) -> Answer {
+    input.iter()
         .map(|history| {
-            return history.process_until_end()
+            return history.clone().process_until_end()
                 .predict_sequences_previous_value()
                 .get_previous_predict_value()
-        }).sum::<i64>()
+        }).sum::<i64>().into()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<History>;
+
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Mirage Maintenance"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<History>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Vec<History>) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Vec<History>) -> Answer {
+        part_two(input)
+    }
 }
 
-struct History {
-    sequences: Vec<Sequence>
+#[derive(Clone)]
+pub struct History {
+    pub sequences: Vec<Sequence>
 }
 
 impl History {
-    fn parse(input_line: &str) -> Self {
-        History { sequences: vec![Sequence::parse(input_line)]}
+    pub fn parse(input_line: &str) -> Result<Self, ParseError> {
+        Ok(History { sequences: vec![Sequence::parse(input_line)?]})
     }
 
     fn has_end_sequence(&self) -> bool {
@@ -114,15 +147,18 @@ impl History {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, PartialOrd, Ord)]
-struct Sequence {
-    values: Vec<i64>
+pub struct Sequence {
+    pub values: Vec<i64>
 }
 
 impl Sequence {
-    fn parse(input_line: &str) -> Self {
-        let numbers:Vec<i64> = input_line.split_whitespace().map(|number| parse_numbers_i64(number).unwrap().1).collect();
+    pub fn parse(input_line: &str) -> Result<Self, ParseError> {
+        let numbers:Vec<i64> = input_line.split_whitespace().map(|number| parse_number(input_line, number)).collect::<Result<_, _>>()?;
+        if numbers.is_empty() {
+            return Err(ParseError::missing(input_line, "a sequence of numbers"));
+        }
 
-        Sequence { values: numbers }
+        Ok(Sequence { values: numbers })
     }
 
     fn is_end(&self) -> bool {
@@ -158,7 +194,7 @@ mod tests {
     fn can_get_next_diff_sequence() {
         let input = r#"0 3 6 9 12 15"#;
 
-        let sequence = Sequence::parse(input);
+        let sequence = Sequence::parse(input).unwrap();
         let next = sequence.next_sequence();
 
         assert_eq!(next.values.len(), 5);
@@ -169,7 +205,7 @@ mod tests {
     fn can_tell_if_sequence_is_end() {
         let input = r#"3 3 3 3 3"#;
 
-        let sequence = Sequence::parse(input);
+        let sequence = Sequence::parse(input).unwrap();
 
         assert_eq!(sequence.is_end(), false);
 
@@ -180,7 +216,7 @@ mod tests {
     fn can_process_history_until_end() {
         let input = r#"0 3 6 9 12 15"#;
 
-        let history = History::parse(input);
+        let history = History::parse(input).unwrap();
 
         let updated_history = history.process_until_end();
         assert_eq!(updated_history.sequences.len(), 3);
@@ -190,7 +226,7 @@ mod tests {
     fn can_predict_next_sequence_value() {
         let input = r#"0 3 6 9 12 15"#;
 
-        let history = History::parse(input);
+        let history = History::parse(input).unwrap();
 
         let updated_history = history.process_until_end().predict_sequences_next_value();
 
@@ -201,7 +237,7 @@ mod tests {
     fn sequence_can_add_prediction() {
         let input = r#"0 3 6 9 12 15"#;
 
-        let seq = Sequence::parse(input);
+        let seq = Sequence::parse(input).unwrap();
 
         let updated_seq = seq.predict_next_value(3);
 
@@ -211,7 +247,7 @@ mod tests {
     #[test]
     fn sequence_can_add_previous_prediction() {
         let input = r#"10 13 16 21 30 45"#;
-        let seq = Sequence::parse(input);
+        let seq = Sequence::parse(input).unwrap();
 
         let updated = seq.predict_previous_value(5);
         assert_eq!(updated.values[0], 5);
@@ -221,7 +257,7 @@ mod tests {
     fn history_can_predict_previous_sequence_value() {
         let input = r#"10 13 16 21 30 45"#;
 
-        let history = History::parse(input);
+        let history = History::parse(input).unwrap();
 
         let updated_history = history.process_until_end().predict_sequences_previous_value();
 
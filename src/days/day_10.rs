@@ -1,25 +1,56 @@
-use std::collections::HashMap;
-use std::fmt::Display;
 use nom::character::complete::char;
-use crate::domain::point::{EAST, NORTH, Point, SOUTH, WEST};
+use crate::domain::direction::Direction;
+use crate::domain::grid::Grid;
+use crate::domain::point::Point;
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+pub fn parse(input: &str) -> Result<PipeGrid, ParseError> {
+    PipeGrid::parse(input)
+}
 
-pub fn part_one(input: String) -> impl Display {
-    let grid = PipeGrid::parse(input);
+pub fn part_one(input: &PipeGrid) -> Answer {
+    walk_node_path(input).into()
+}
 
-    walk_node_path(grid)
+pub fn part_two(input: &PipeGrid) -> Answer {
+    get_inner_area(input).into()
 }
 
-pub fn part_two(input: String) -> impl Display {
-    get_inner_area(PipeGrid::parse(input))
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = PipeGrid;
+
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Pipe Maze"
+    }
+
+    fn parse(&self, input: &str) -> Result<PipeGrid, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &PipeGrid) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &PipeGrid) -> Answer {
+        part_two(input)
+    }
 }
 
-fn get_inner_area(pipe_grid: PipeGrid) -> usize {
+pub fn get_inner_area(pipe_grid: &PipeGrid) -> usize {
     let mut inside:Vec<Point> = Vec::new();
     let corners = pipe_grid.get_corners_of_loop();
     let path = pipe_grid.get_walk_path();
-    for map_entry in pipe_grid.grid {
-        if !path.contains(&map_entry.0) && tile_is_inside(map_entry.0, &corners) {
-            inside.push(map_entry.0.clone());
+    for point in pipe_grid.grid.points() {
+        if !path.contains(&point) && tile_is_inside(point, &corners) {
+            inside.push(point);
         }
     }
 
@@ -48,7 +79,7 @@ fn tile_is_inside(position: Point, vertices: &Vec<Point>) -> bool {
     return is_inside
 }
 
-fn walk_node_path(pipe_grid: PipeGrid) -> usize {
+pub fn walk_node_path(pipe_grid: &PipeGrid) -> usize {
     let start_positions = pipe_grid.get_start_connected_nodes();
     let mut walk_map:Vec<Point> = Vec::new();
     let start_node = pipe_grid.get_start_node();
@@ -57,8 +88,7 @@ fn walk_node_path(pipe_grid: PipeGrid) -> usize {
     walk_map.push(current_node);
 
     while current_node != start_node {
-        let pipe = pipe_grid.grid.get(&current_node).unwrap();
-        let next_nodes:Vec<Point> = pipe.get_connected_positions().iter().filter(|connected| !walk_map.contains(connected)).map(|c| *c).collect();
+        let next_nodes:Vec<Point> = pipe_grid.get_connected_positions(current_node).iter().filter(|connected| !walk_map.contains(connected)).map(|c| *c).collect();
         if(next_nodes.len() == 0) {
             break;
         }
@@ -70,58 +100,28 @@ fn walk_node_path(pipe_grid: PipeGrid) -> usize {
     walk_map.len() / 2
 }
 
-struct PipeGrid {
-    grid: HashMap<Point, Node>,
-    size: Point
+pub struct PipeGrid {
+    pub grid: Grid<char>,
+    pub start: Point
 }
 
 impl PipeGrid {
-    fn parse(input: String) -> Self {
-        let mut map:HashMap<Point, Node> = HashMap::new();
-        let mut y_index = (input.lines().count() as i32) - 1;
-
-        let mut total_size:Point = Point::parse(input.lines().nth(0).unwrap().chars().count(), y_index as usize);
-        for (y, line) in input.lines().enumerate() {
-            let mut x_index = 0;
-            for (x, node) in line.chars().enumerate() {
-                map.insert(Point::new(x_index,y_index), Node {pos: Point::new(x_index, y_index), icon: node, start: node == 'S'});
-                x_index = x_index + 1;
-            }
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input, "one of |-LJ7F.S", |node| "|-LJ7F.S".contains(node).then_some(node))?;
+        let start = grid.position(|node| *node == 'S').ok_or_else(|| ParseError::new("there is no starting tile S"))?;
 
-            y_index = y_index - 1;
-        }
-
-        PipeGrid { grid: map, size: total_size }
+        Ok(PipeGrid { grid, start })
     }
 
     fn get_start_node(&self) -> Point {
-        for map_entry in &self.grid {
-            if (map_entry.1.start == true) {
-                return map_entry.1.pos;
-            }
-        }
-
-        Point::new(0,0)
+        self.start
     }
 
     fn get_start_connected_nodes(&self) -> Vec<Point> {
-        let mut connected_nodes:Vec<Point> = Vec::new();
-        for map_entry in &self.grid {
-            if(map_entry.1.start == true){
-                let start_position:Point = map_entry.1.pos.clone();
-                start_position.get_cardinal_neighbors().iter().for_each(|point| {
-                    if(!self.grid.contains_key(point)) {
-                        return;
-                    }
-                    let connected = self.grid.get(point).unwrap();
-                    if *&connected.get_connected_positions().iter().filter(|p| p.x == start_position.x && p.y == start_position.y).count() > 0 {
-                        connected_nodes.push(Point::new(point.x, point.y));
-                    }
-                });
-            }
-        }
-
-        connected_nodes
+        let start_position = self.start;
+        self.grid.neighbors(start_position)
+            .filter(|point| self.get_connected_positions(*point).contains(&start_position))
+            .collect()
     }
 
     fn get_walk_path(&self) -> Vec<Point> {
@@ -133,8 +133,7 @@ impl PipeGrid {
         walk_map.push(current_node);
 
         while current_node != start_node {
-            let pipe = self.grid.get(&current_node).unwrap();
-            let next_nodes:Vec<Point> = pipe.get_connected_positions().iter().filter(|connected| !walk_map.contains(connected)).map(|c| *c).collect();
+            let next_nodes:Vec<Point> = self.get_connected_positions(current_node).iter().filter(|connected| !walk_map.contains(connected)).map(|c| *c).collect();
             if(next_nodes.len() == 0) {
                 break;
             }
@@ -157,11 +156,10 @@ impl PipeGrid {
         walk_map.push(current_node);
 
         while current_node != start_node {
-            let pipe = self.grid.get(&current_node).unwrap();
-            if pipe.is_corner() {
+            if self.is_corner(current_node) {
                 corner_list.push(current_node);
             }
-            let next_nodes:Vec<Point> = pipe.get_connected_positions().iter().filter(|connected| !walk_map.contains(connected)).map(|c| *c).collect();
+            let next_nodes:Vec<Point> = self.get_connected_positions(current_node).iter().filter(|connected| !walk_map.contains(connected)).map(|c| *c).collect();
             if(next_nodes.len() == 0) {
                 break;
             }
@@ -172,29 +170,13 @@ impl PipeGrid {
 
         corner_list
     }
-}
 
-struct Node {
-    pos: Point,
-    icon: char,
-    start: bool
-}
-impl Node {
-    fn get_connected_positions(&self) -> Vec<Point> {
-        return match self.icon {
-            '|' => vec![self.pos + NORTH, self.pos + SOUTH],
-            '-' => vec![self.pos + EAST, self.pos + WEST],
-            '7' => vec![self.pos + SOUTH, self.pos + WEST],
-            'L' => vec![self.pos + EAST, self.pos + NORTH],
-            'J' => vec![self.pos + NORTH, self.pos + WEST],
-            'F' => vec![self.pos + SOUTH, self.pos + EAST],
-            '.' => vec![],
-            _ => vec![],
-        }
+    fn get_connected_positions(&self, pos: Point) -> Vec<Point> {
+        pipe_connections(self.grid[pos]).iter().map(|direction| pos + *direction).collect()
     }
 
-    fn is_corner(&self) -> bool {
-        return match self.icon {
+    fn is_corner(&self, pos: Point) -> bool {
+        return match self.grid[pos] {
             '|' => false,
             '-' => false,
             _ => true
@@ -202,15 +184,28 @@ impl Node {
     }
 }
 
+// The two ways out of each piece of pipe
+fn pipe_connections(pipe: char) -> &'static [Direction] {
+    match pipe {
+        '|' => &[Direction::North, Direction::South],
+        '-' => &[Direction::East, Direction::West],
+        '7' => &[Direction::South, Direction::West],
+        'L' => &[Direction::East, Direction::North],
+        'J' => &[Direction::North, Direction::West],
+        'F' => &[Direction::South, Direction::East],
+        _ => &[]
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::days::day_10::{get_inner_area, Node, PipeGrid, walk_node_path};
+    use crate::days::day_10::{get_inner_area, PipeGrid, walk_node_path};
     use crate::domain::point::Point;
 
     #[test]
     fn can_get_connected_neighbors() {
-        let node = Node { pos: Point::new(1, 1), icon: '-', start: false };
-        let connected = node.get_connected_positions();
+        let pipe_grid = PipeGrid::parse("S..\n.-.\n...").unwrap();
+        let connected = pipe_grid.get_connected_positions(Point::new(1, 1));
 
         assert_eq!(connected[0], Point::new(2,1));
         assert_eq!(connected[1], Point::new(0,1 ));
@@ -225,9 +220,9 @@ SJLL7
 |F--J
 LJ.LJ"#;
 
-        let pipe_grid = PipeGrid::parse(input.to_string());
+        let pipe_grid = PipeGrid::parse(input).unwrap();
 
-        assert_eq!(walk_node_path(pipe_grid), 8usize);
+        assert_eq!(walk_node_path(&pipe_grid), 8usize);
     }
 
     #[test]
@@ -241,9 +236,9 @@ LJ.LJ"#;
 .|..|.|..|.
 .L--J.L--J.
 ..........."#;
-        let pipe_grid = PipeGrid::parse(input.to_string());
+        let pipe_grid = PipeGrid::parse(input).unwrap();
 
-        let inner_tiles = get_inner_area(pipe_grid);
+        let inner_tiles = get_inner_area(&pipe_grid);
 
         assert_eq!(inner_tiles, 4usize);
     }
@@ -261,9 +256,9 @@ L---JF-JLJ.||-FJLJJ7
 L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L"#;
 
-        let pipe_grid = PipeGrid::parse(input.to_string());
+        let pipe_grid = PipeGrid::parse(input).unwrap();
 
-        let inner_tiles = get_inner_area(pipe_grid);
+        let inner_tiles = get_inner_area(&pipe_grid);
 
         assert_eq!(inner_tiles, 10usize);
     }
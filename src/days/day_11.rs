@@ -1,214 +1,137 @@
-use std::collections::HashMap;
-use std::fmt::Display;
-use crate::domain::point::Point;
-
-pub fn part_one(input: String) -> impl Display {
-    let mut space_image = SpaceImage::parse(input);;
-    space_image = space_image.expand_empty(1);
-    get_combined_distances(space_image.get_galaxy_positions())
+use crate::domain::grid::Grid;
+use crate::domain::point::{Point, PointLarge};
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+pub fn parse(input: &str) -> Result<SpaceImage, ParseError> {
+    SpaceImage::parse(input)
 }
 
-pub fn part_two(input: String) -> impl Display {
-    let mut space_image = SpaceImage::parse(input);;
-    let galaxies = space_image.get_expanded_galaxies(1000000);
-    get_combined_distances(galaxies)
+pub fn part_one(input: &SpaceImage) -> Answer {
+    let space_image = input.expand_empty(1);
+    get_combined_distances(space_image.get_galaxy_positions()).into()
 }
 
-fn get_combined_distances(positions: Vec<Point>) -> i64 {
-    let mut running_total = 0;
-    for i in 0..positions.len() {
-        for j in (i + 1)..positions.len() {
-            running_total += positions[i].manhattan_distance(positions[j]) as i64;
-        }
-    }
-
-    running_total
+pub fn part_two(input: &SpaceImage) -> Answer {
+    let galaxies = input.get_expanded_galaxies(1000000);
+    get_combined_distances(galaxies).into()
 }
 
-struct SpaceImage {
-    grid: HashMap<Point, char>,
-    size: Point
-}
+pub struct Day11;
 
-impl SpaceImage {
-    fn parse(input: String) -> Self {
-        let mut y_index = (input.lines().count() as i32);
-        let mut map:HashMap<Point, char> = HashMap::new();
-        let mut total_size:Point = Point::parse(input.lines().nth(0).unwrap().chars().count(), y_index as usize);
-
-        for (y, line) in input.lines().enumerate() {
-            let mut x_index = 0;
-            for (x, node) in line.chars().enumerate() {
-                map.insert(Point::new(x_index, y as i32), node);
-                x_index = x_index + 1;
-            }
+impl Solution for Day11 {
+    type Input = SpaceImage;
 
-        }
+    fn day(&self) -> u32 {
+        11
+    }
 
-        SpaceImage { grid: map, size: total_size }
+    fn title(&self) -> &'static str {
+        "Cosmic Expansion"
     }
 
-    fn expand_empty(&self, amount: i32) -> Self {
-        let mut empty_rows:Vec<i32> = Vec::new();
-        let mut empty_columns:Vec<i32> = Vec::new();
-        let mut galaxies = self.get_galaxy_positions();
-
-        for y in 0..self.size.y {
-            let mut empty_row:bool = true;
-            for x in 0..self.size.x {
-                if(self.grid.get(&Point::new(x,y)).unwrap() == &'#') {
-                    empty_row = false;
-                }
-            }
+    fn parse(&self, input: &str) -> Result<SpaceImage, ParseError> {
+        parse(input)
+    }
 
-            if empty_row  {
-                empty_rows.push(y);
-            }
-        }
+    fn part_one(&self, input: &SpaceImage) -> Answer {
+        part_one(input)
+    }
 
-        for x in 0..self.size.x {
-            let mut empty_column:bool = true;
-            for y in 0..self.size.y {
-                if self.grid.get(&Point::new(x,y)).unwrap() == &'#' {
-                    empty_column = false;
-                }
-            }
+    fn part_two(&self, input: &SpaceImage) -> Answer {
+        part_two(input)
+    }
+}
 
-            if empty_column  {
-                empty_columns.push(x);
-            }
+fn get_combined_distances(positions: Vec<PointLarge>) -> i64 {
+    let mut running_total = 0;
+    for i in 0..positions.len() {
+        for j in (i + 1)..positions.len() {
+            running_total += positions[i].manhattan_distance(positions[j]);
         }
-
-
-
-        let mut space_image = SpaceImage { grid: self.grid.clone(), size: self.size };
-
-            for row_index in 0..empty_rows.len() {
-                space_image = space_image.add_empty_row(empty_rows[row_index] + (row_index as i32 * amount));
-            }
-
-            for col_index in 0..empty_columns.len() {
-                for i in 0..amount {
-                    space_image = space_image.add_empty_column(empty_columns[col_index] + (col_index as i32 * amount));
-                }
-            }
-
-        space_image
     }
 
-    fn get_expanded_galaxies(&self, amount: i32) -> Vec<Point> {
-
-        let mut expand_amount = amount - 1;
-        let mut empty_rows:Vec<i32> = Vec::new();
-        let mut empty_columns:Vec<i32> = Vec::new();
-        let mut galaxies = self.get_galaxy_positions();
+    running_total
+}
 
-        for y in 0..self.size.y {
-            let mut empty_row:bool = true;
-            for x in 0..self.size.x {
-                if(self.grid.get(&Point::new(x,y)).unwrap() == &'#') {
-                    empty_row = false;
-                }
-            }
+pub struct SpaceImage {
+    pub grid: Grid<char>
+}
 
-            if empty_row  {
-                empty_rows.push(y);
-            }
-        }
+impl SpaceImage {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input, ". or #", |pixel| (pixel == '.' || pixel == '#').then_some(pixel))?;
 
-        for x in 0..self.size.x {
-            let mut empty_column:bool = true;
-            for y in 0..self.size.y {
-                if self.grid.get(&Point::new(x,y)).unwrap() == &'#' {
-                    empty_column = false;
-                }
-            }
+        Ok(SpaceImage { grid })
+    }
 
-            if empty_column  {
-                empty_columns.push(x);
-            }
-        }
+    fn empty_rows(&self) -> Vec<i32> {
+        (0..self.grid.height() as i32)
+            .filter(|y| !self.grid.row(*y).unwrap().contains(&'#'))
+            .collect()
+    }
 
-        let mut new_galaxy_positions:Vec<Point> = Vec::new();
+    fn empty_columns(&self) -> Vec<i32> {
+        (0..self.grid.width() as i32)
+            .filter(|x| !self.grid.column(*x).unwrap().any(|pixel| *pixel == '#'))
+            .collect()
+    }
 
-        for galaxy in galaxies {
-            let mut new_position:Point = galaxy.clone();
-            for row_index in 0..empty_rows.len() {
-                if galaxy.y > empty_rows[row_index] {
-                    new_position = Point::new(new_position.x, new_position.y + expand_amount)
+    fn expand_empty(&self, amount: i32) -> Self {
+        let empty_columns = self.empty_columns();
+        let mut rows:Vec<Vec<char>> = Vec::new();
+
+        for row in self.grid.rows() {
+            let mut expanded_row:Vec<char> = Vec::new();
+            for (x, pixel) in row.iter().enumerate() {
+                expanded_row.push(*pixel);
+                if empty_columns.contains(&(x as i32)) {
+                    expanded_row.extend(std::iter::repeat('.').take(amount as usize));
                 }
             }
 
-            for col_index in 0..empty_columns.len() {
-                if galaxy.x > empty_columns[col_index] {
-                    new_position = Point::new(new_position.x + expand_amount, new_position.y)
-                }
+            let copies = if row.contains(&'#') { 1 } else { 1 + amount as usize };
+            for _ in 0..copies {
+                rows.push(expanded_row.clone());
             }
-
-            new_galaxy_positions.push(new_position);
         }
 
-        new_galaxy_positions
+        SpaceImage { grid: Grid::from_rows(rows).unwrap() }
     }
 
-    fn add_empty_row(&self, index: i32) -> Self {
+    fn get_expanded_galaxies(&self, amount: i64) -> Vec<PointLarge> {
+        let expand_amount = amount - 1;
+        let empty_rows = self.empty_rows();
+        let empty_columns = self.empty_columns();
 
-        let mut new_grid:HashMap<Point, char> = HashMap::new();
-        for i in 0..self.size.x {
-            new_grid.insert(Point::new(i, index), '.');
-        }
+        self.get_galaxy_positions().into_iter().map(|galaxy| {
+            let rows_before = empty_rows.iter().filter(|y| galaxy.y > **y as i64).count() as i64;
+            let columns_before = empty_columns.iter().filter(|x| galaxy.x > **x as i64).count() as i64;
 
-        for (point, char) in &self.grid {
-            if point.y >= index  {
-                new_grid.insert(Point::new(point.x, point.y + 1), *char);
-            }
-            else {
-                new_grid.insert(Point::new(point.x, point.y), *char);
-            }
-        }
-
-        SpaceImage { grid: new_grid, size: Point::new(self.size.x, self.size.y + 1)}
+            Point::new(galaxy.x + columns_before * expand_amount, galaxy.y + rows_before * expand_amount)
+        }).collect()
     }
-    fn add_empty_column(&self, index: i32) -> Self {
 
-        let mut new_grid:HashMap<Point, char> = HashMap::new();
-        for i in 0..self.size.y {
-            new_grid.insert(Point::new(index, i), '.');
-        }
-
-        for (point, char) in &self.grid {
-            if point.x >= index  {
-                new_grid.insert(Point::new(point.x + 1, point.y), *char);
-            }
-            else {
-                new_grid.insert(Point::new(point.x, point.y), *char);
-            }
-        }
+    fn add_empty_column(&self, index: i32) -> Self {
+        let rows = self.grid.rows().map(|row| {
+            let mut row = row.to_vec();
+            row.insert(index as usize, '.');
+            row
+        }).collect();
 
-        SpaceImage { grid: new_grid, size: Point::new(self.size.x + 1, self.size.y)}
+        SpaceImage { grid: Grid::from_rows(rows).unwrap() }
     }
 
-    fn get_galaxy_positions(&self) -> Vec<Point> {
-        let mut positions:Vec<Point> = Vec::new();
-        for y in 0..self.size.y {
-            for x in 0..self.size.x {
-                if self.grid.get(&Point::new(x, y)).unwrap() == &'#' {
-                    positions.push(Point::new(x, y));
-                }
-            }
-        }
-
-        positions
+    fn get_galaxy_positions(&self) -> Vec<PointLarge> {
+        self.grid.iter()
+            .filter(|(_, pixel)| **pixel == '#')
+            .map(|(point, _)| point.cast())
+            .collect()
     }
 
     fn print_out(&self) {
-        for y in 0..self.size.y {
-
-            for x in 0..self.size.x {
-                print!("{}", self.grid.get(&Point::new(x, y)).unwrap())
-            }
-            println!("");
-        }
+        println!("{}", self.grid);
     }
 }
 
@@ -229,14 +152,14 @@ mod tests {
 .......#..
 #...#....."#;
 
-        let space_image = SpaceImage::parse(input.to_string());
+        let space_image = SpaceImage::parse(input).unwrap();
 
         space_image.print_out();
         let new_space_image = space_image.add_empty_column(3);
         println!("");
         new_space_image.print_out();
 
-        assert_eq!(new_space_image.size.x, space_image.size.x + 1);
+        assert_eq!(new_space_image.grid.width(), space_image.grid.width() + 1);
     }
 
     #[test]
@@ -252,15 +175,15 @@ mod tests {
 .......#..
 #...#....."#;
 
-        let space_image = SpaceImage::parse(input.to_string());
+        let space_image = SpaceImage::parse(input).unwrap();
 
         space_image.print_out();
         let new_space_image = space_image.expand_empty(1);
         println!("");
         new_space_image.print_out();
 
-        assert_eq!(new_space_image.size.y, space_image.size.y + 2);
-        assert_eq!(new_space_image.size.x, space_image.size.x + 3);
+        assert_eq!(new_space_image.grid.height(), space_image.grid.height() + 2);
+        assert_eq!(new_space_image.grid.width(), space_image.grid.width() + 3);
     }
 
     #[test]
@@ -276,7 +199,7 @@ mod tests {
 .......#..
 #...#....."#;
 
-        let space_image = SpaceImage::parse(input.to_string());
+        let space_image = SpaceImage::parse(input).unwrap();
 
         let new_space_image = space_image.expand_empty(1);
         let total = get_combined_distances(new_space_image.get_galaxy_positions());
@@ -297,7 +220,7 @@ mod tests {
 .......#..
 #...#....."#;
 
-        let space_image = SpaceImage::parse(input.to_string());
+        let space_image = SpaceImage::parse(input).unwrap();
 
         let galaxies = space_image.get_expanded_galaxies(2);
         let total = get_combined_distances(galaxies);
@@ -318,7 +241,7 @@ mod tests {
 .......#..
 #...#....."#;
 
-        let space_image = SpaceImage::parse(input.to_string());
+        let space_image = SpaceImage::parse(input).unwrap();
 
         let galaxies = space_image.get_expanded_galaxies(10);
         let total = get_combined_distances(galaxies);
@@ -339,7 +262,7 @@ mod tests {
 .......#..
 #...#....."#;
 
-        let space_image = SpaceImage::parse(input.to_string());
+        let space_image = SpaceImage::parse(input).unwrap();
 
         let galaxies = space_image.get_expanded_galaxies(100);
         let total = get_combined_distances(galaxies);
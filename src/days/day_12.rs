@@ -1,26 +1,57 @@
-use std::fmt::Display;
-use crate::tools::parse_numbers;
+use crate::tools::parse_number;
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
 
-pub fn part_one(input: String) -> impl Display {
-    let configuration_sequences:Vec<ConfigurationSequence> = input.lines().map(|line| ConfigurationSequence::parse(line)).collect();
+pub fn parse(input: &str) -> Result<Vec<ConfigurationSequence>, ParseError> {
+    parse_lines(input, ConfigurationSequence::parse)
+}
 
-    let total = configuration_sequences.iter().map(|seq| {
+pub fn part_one(input: &Vec<ConfigurationSequence>) -> Answer {
+    let total = input.iter().map(|seq| {
         return arrangements(seq.condition_records.clone(), seq.contiguous_groups.clone(), 0, seq.condition_records.len() as i32);
     }).sum::<i64>();
 
-    total
+    total.into()
 }
 
-pub fn part_two(input: String) -> impl Display {
-    let configuration_sequences:Vec<ConfigurationSequence> = input.lines().map(|line| ConfigurationSequence::unfold(line)).collect();
+pub fn part_two(input: &Vec<ConfigurationSequence>) -> Answer {
+    let configuration_sequences:Vec<ConfigurationSequence> = input.iter().map(|seq| seq.unfolded()).collect();
 
     let total = configuration_sequences.iter().map(|seq| {
         return arrangements(seq.condition_records.clone(), seq.contiguous_groups.clone(), 0, seq.condition_records.len() as i32);
     }).sum::<i64>();
 
-    total
+    total.into()
 }
-fn sequence_arrangements(sequence: ConfigurationSequence) -> i64 {
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<ConfigurationSequence>;
+
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn title(&self) -> &'static str {
+        "Hot Springs"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<ConfigurationSequence>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Vec<ConfigurationSequence>) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Vec<ConfigurationSequence>) -> Answer {
+        part_two(input)
+    }
+}
+
+pub fn sequence_arrangements(sequence: ConfigurationSequence) -> i64 {
     return arrangements(sequence.condition_records.clone(), sequence.contiguous_groups.clone(), 0, sequence.condition_records.len() as i32);
 }
 fn arrangements(record: Vec<char>, groups: Vec<i32>, offset:i32, length:i32) -> i64 {
@@ -115,42 +146,42 @@ fn no_section(record: Vec<char>, offset:i32, length:i32) -> i64 {
     return 1
 }
 
-struct ConfigurationSequence {
-    condition_records: Vec<char>,
-    contiguous_groups: Vec<i32>
+pub struct ConfigurationSequence {
+    pub condition_records: Vec<char>,
+    pub contiguous_groups: Vec<i32>
 }
 
 impl ConfigurationSequence {
-    fn parse(input_line: &str) -> Self {
+    pub fn parse(input_line: &str) -> Result<Self, ParseError> {
         let mut split = input_line.split_whitespace();
-        let first_half = split.nth(0).unwrap();
-        let second_half = split.nth(0).unwrap();
+        let first_half = split.next().ok_or_else(|| ParseError::missing(input_line, "condition records"))?;
+        let second_half = split.next().ok_or_else(|| ParseError::missing(input_line, "the damaged group sizes"))?;
 
+        if let Some((offset, record)) = first_half.char_indices().find(|(_, record)| !".#?".contains(*record)) {
+            return Err(ParseError::at(input_line, &first_half[offset..], format!("expected ., # or ?, found {:?}", record)));
+        }
         let characters:Vec<char> = first_half.chars().collect();
-        let contiguous_groups:Vec<i32> = second_half.split(',').map(|section| parse_numbers(section).unwrap().1).collect();
+        let contiguous_groups:Vec<i32> = second_half.split(',').map(|section| parse_number(input_line, section)).collect::<Result<_, _>>()?;
 
-        ConfigurationSequence { condition_records: characters, contiguous_groups }
+        Ok(ConfigurationSequence { condition_records: characters, contiguous_groups })
     }
 
-    fn unfold(input_line:&str) -> Self {
-        let mut split = input_line.split_whitespace();
-        let first_half = split.nth(0).unwrap();
-        let second_half = split.nth(0).unwrap();
+    fn unfold(input_line:&str) -> Result<Self, ParseError> {
+        Ok(ConfigurationSequence::parse(input_line)?.unfolded())
+    }
 
-        let mut unfolded_records:Vec<&str> = Vec::new();
-        let mut unfolded_groups:Vec<&str> = Vec::new();
+    // Five copies of the records joined by '?', and five copies of the groups
+    fn unfolded(&self) -> Self {
+        let mut unfolded:Vec<char> = Vec::new();
+        let mut groups:Vec<i32> = Vec::new();
         for i in 0..5 {
-            unfolded_records.push(first_half);
-            unfolded_groups.push(second_half);
+            if i > 0 {
+                unfolded.push('?');
+            }
+            unfolded.extend(&self.condition_records);
+            groups.extend(&self.contiguous_groups);
         }
 
-        let unfolded:Vec<char> = unfolded_records.join("?").chars().collect();
-        let groups:Vec<i32> = unfolded_groups
-            .join(",")
-            .split(',')
-            .map(|section| parse_numbers(section).unwrap().1)
-            .collect();
-
         ConfigurationSequence { condition_records: unfolded, contiguous_groups: groups }
     }
 
@@ -164,7 +195,7 @@ mod tests {
     fn can_get_configuration_amount() {
         let input_line = r#"?###???????? 3,2,1"#;
 
-        let sequence = ConfigurationSequence::parse(input_line);
+        let sequence = ConfigurationSequence::parse(input_line).unwrap();
         let amount = sequence_arrangements(sequence);
 
         assert_eq!(amount, 10);
@@ -175,7 +206,7 @@ mod tests {
     fn can_unfold_record() {
         let input_line = r#"???.### 1,1,3"#;
 
-        let sequence = ConfigurationSequence::unfold(input_line);
+        let sequence = ConfigurationSequence::unfold(input_line).unwrap();
         let s:String = sequence.condition_records.iter().collect();
         assert_eq!(s, "???.###????.###????.###????.###????.###");
     }
@@ -184,7 +215,7 @@ mod tests {
     fn can_calculate_unfolded_configuration_amount() {
         let input_line = r#"?###???????? 3,2,1"#;
 
-        let sequence = ConfigurationSequence::unfold(input_line);
+        let sequence = ConfigurationSequence::unfold(input_line).unwrap();
         let amount = sequence_arrangements(sequence);
 
         assert_eq!(amount, 506250);
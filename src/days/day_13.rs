@@ -1,14 +1,22 @@
-use std::fmt::Display;
-
-pub fn part_one(input: String) -> impl Display {
-    summarize(input, false)
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+pub fn parse(input: &str) -> Result<Vec<MirrorPattern>, ParseError> {
+    input.split_terminator("\n\n")
+        .map(|block| MirrorPattern::parse(block).map_err(|error| error.within(input, block)))
+        .collect()
 }
 
-fn summarize(input:String, smudging:bool) -> i32 {
-    let patterns:Vec<MirrorPattern> = input.split_terminator("\n\n")
-        .map(|block| MirrorPattern::parse(block)).collect();
+pub fn part_one(input: &Vec<MirrorPattern>) -> Answer {
+    summarize(input, false).into()
+}
 
+pub fn part_two(input: &Vec<MirrorPattern>) -> Answer {
+    summarize(input, true).into()
+}
 
+pub fn summarize(patterns: &Vec<MirrorPattern>, smudging:bool) -> i32 {
     let mut running_total = 0;
     patterns.iter().for_each(|pattern| {
         running_total += pattern.get_summary_total(smudging);
@@ -17,19 +25,49 @@ fn summarize(input:String, smudging:bool) -> i32 {
     running_total
 }
 
-pub fn part_two(input: String) -> impl Display {
-    summarize(input, true)
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<MirrorPattern>;
+
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Point of Incidence"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<MirrorPattern>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Vec<MirrorPattern>) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Vec<MirrorPattern>) -> Answer {
+        part_two(input)
+    }
 }
 
-struct MirrorPattern {
-    vertical: Vec<Vec<char>>,
-    horizontal: Vec<Vec<char>>
+pub struct MirrorPattern {
+    pub vertical: Vec<Vec<char>>,
+    pub horizontal: Vec<Vec<char>>
 }
 
 impl MirrorPattern {
-    fn parse(input_block: &str) -> Self {
+    pub fn parse(input_block: &str) -> Result<Self, ParseError> {
+        let line_len = input_block.lines().peekable().nth(0).unwrap_or_default().len();
+        for (index, line) in input_block.lines().enumerate() {
+            if let Some((offset, tile)) = line.char_indices().find(|(_, tile)| *tile != '.' && *tile != '#') {
+                return Err(ParseError::at(line, &line[offset..], format!("expected . or #, found {:?}", tile)).on_line(index + 1));
+            }
+            if line.len() != line_len {
+                return Err(ParseError::at(line, line, format!("expected a row of {} tiles, found {}", line_len, line.len())).on_line(index + 1));
+            }
+        }
         let horizontal: Vec<Vec<char>> = input_block.lines().map(|line| line.chars().collect()).collect();
-        let line_len = input_block.lines().peekable().nth(0).unwrap().len();
 
         let mut vertical: Vec<Vec<char>> = Vec::new();
         let lines_count = input_block.lines().count();
@@ -51,7 +89,7 @@ impl MirrorPattern {
         }
 
 
-        MirrorPattern { horizontal, vertical }
+        Ok(MirrorPattern { horizontal, vertical })
     }
 
     fn get_summary_total(&self, smudging:bool) -> i32 {
@@ -152,7 +190,7 @@ impl MirrorPattern {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day_13::{MirrorPattern, summarize};
+    use crate::days::day_13::{MirrorPattern, parse, summarize};
 
     #[test]
     fn find_vertical_reflect_pattern() {
@@ -164,7 +202,7 @@ mod tests {
 ..##..##.
 #.#.##.#."#;
 
-        let mirror_pattern = MirrorPattern::parse(input);
+        let mirror_pattern = MirrorPattern::parse(input).unwrap();
 
         assert_eq!(mirror_pattern.find_vertical_reflect_point(false).unwrap(), 5);
     }
@@ -179,7 +217,7 @@ mod tests {
 ..##..###
 #....#..#"#;
 
-        let mirror_pattern = MirrorPattern::parse(input);
+        let mirror_pattern = MirrorPattern::parse(input).unwrap();
 
         assert_eq!(mirror_pattern.find_horizontal_reflect_point(false).unwrap(), 4);
     }
@@ -202,7 +240,7 @@ mod tests {
 ..##..###
 #....#..#"#;
 
-        let summary = summarize(input.to_string(), false);
+        let summary = summarize(&parse(input).unwrap(), false);
 
         assert_eq!(summary, 405);
     }
@@ -217,7 +255,7 @@ mod tests {
 ..##..###
 #....#..#"#;
 
-        let mirror_pattern = MirrorPattern::parse(input);
+        let mirror_pattern = MirrorPattern::parse(input).unwrap();
 
         assert_eq!(mirror_pattern.get_summary_total(false), 400);
     }
@@ -232,7 +270,7 @@ mod tests {
 ..##..##.
 #.#.##.#."#;
 
-        let mirror_pattern = MirrorPattern::parse(input);
+        let mirror_pattern = MirrorPattern::parse(input).unwrap();
 
         assert_eq!(mirror_pattern.find_horizontal_reflect_point(true).unwrap(), 3);
     }
@@ -247,7 +285,7 @@ mod tests {
 ..##..###
 #....#..#"#;
 
-        let mirror_pattern = MirrorPattern::parse(input);
+        let mirror_pattern = MirrorPattern::parse(input).unwrap();
 
         assert_eq!(mirror_pattern.find_horizontal_reflect_point(true).unwrap(), 1);
     }
@@ -270,8 +308,17 @@ mod tests {
 ..##..###
 #....#..#"#;
 
-        let summary = summarize(input.to_string(), true);
+        let summary = summarize(&parse(input).unwrap(), true);
 
         assert_eq!(summary, 400);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parse_errors_point_into_the_right_pattern() {
+        let input = "#.##..##.\n..#.##.#.\n\n#...##..#\n#....#..#\n..##..#x#";
+
+        let error = parse(input).err().unwrap();
+
+        assert_eq!((error.line, error.column), (Some(6), Some(8)));
+    }
+}
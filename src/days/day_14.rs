@@ -1,18 +1,22 @@
-use std::collections::HashMap;
 use std::fmt;
-use std::fmt::Display;
-use std::str::FromStr;
-use crate::domain::point::{NORTH, SOUTH, Point, WEST, EAST};
-
-pub fn part_one(input: String) -> impl Display {
-    let mut grid = Board::parse(input);
-    let update = grid.tilt(NORTH);
-    update.print();
-    update.get_board_load()
+use crate::domain::direction::Direction;
+use crate::domain::grid::Grid;
+use crate::domain::point::Point;
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+pub fn parse(input: &str) -> Result<Board, ParseError> {
+    Board::parse(input)
 }
 
-pub fn part_two(input: String) -> impl Display {
-    let mut grid = Board::parse(input);
+pub fn part_one(input: &Board) -> Answer {
+    let update = input.tilt(Direction::North);
+    update.get_board_load().into()
+}
+
+pub fn part_two(input: &Board) -> Answer {
+    let mut grid = input.clone();
     let mut seen = vec![grid.grid.clone()];
 
     loop {
@@ -21,28 +25,49 @@ pub fn part_two(input: String) -> impl Display {
             let cycle_len = seen.len() - position;
             let remaining = position + (1_000_000_000 - position) % cycle_len;
 
-            println!("{}", weight_grid(&seen[remaining]));
-            return weight_grid(&seen[remaining]);
+            return weight_grid(&seen[remaining]).into();
         }
 
         seen.push(grid.grid.clone());
     }
 }
 
-fn weight_grid(grid: &HashMap<Point, Entity>) -> i32 {
-    let mut running_total = 0;
-    grid.iter().for_each(|(pos, entity)| {
-        if matches!(entity, Entity::Sphere) {
-            running_total += pos.y + 1;
-        }
-    });
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Board;
+
+    fn day(&self) -> u32 {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Parabolic Reflector Dish"
+    }
+
+    fn parse(&self, input: &str) -> Result<Board, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Board) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Board) -> Answer {
+        part_two(input)
+    }
+}
 
-    running_total
+pub fn weight_grid(grid: &Grid<Entity>) -> i32 {
+    grid.iter()
+        .filter(|(_, entity)| matches!(entity, Entity::Sphere))
+        .map(|(pos, _)| pos.y + 1)
+        .sum()
 }
 
 
 #[derive(Copy, Clone, PartialEq, Eq)]
-enum Entity {
+pub enum Entity {
     Sphere,
     Cube,
     Empty
@@ -59,80 +84,48 @@ impl fmt::Display for Entity {
 }
 
 
-struct Board {
-    grid: HashMap<Point, Entity>,
-    total_size: Point
+#[derive(Clone)]
+pub struct Board {
+    pub grid: Grid<Entity>
 }
 
 impl Board {
-    fn parse(input: String) -> Self {
-        let mut y_index = (input.lines().count() as i32);
-        let mut map:HashMap<Point, Entity> = HashMap::new();
-        let mut total_size:Point = Point::parse(input.lines().nth(0).unwrap().chars().count(), y_index as usize);
-
-        y_index -= 1;
-        for (y, line) in input.lines().enumerate() {
-            for (x, node) in line.chars().enumerate() {
-                let entity = match node {
-                    '#' => Entity::Cube,
-                    'O' => Entity::Sphere,
-                    _ => Entity::Empty
-                };
-
-                map.insert(Point::new(x as i32, y_index as i32), entity);
-            }
-            y_index = y_index - 1;
-
-        }
-
-        Board { grid: map, total_size }
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input, "#, O or .", |node| match node {
+            '#' => Some(Entity::Cube),
+            'O' => Some(Entity::Sphere),
+            '.' => Some(Entity::Empty),
+            _ => None
+        })?;
+
+        Ok(Board { grid })
     }
 
     fn print(&self) {
-
-        for y in (0..self.total_size.y).rev() {
-            for x in 0..self.total_size.x {
-                print!("{}", self.grid.get(&Point::new(x,y)).unwrap());
-            }
-            println!("");
-        }
+        println!("{}", self.grid);
     }
 
-    
-    fn tilt(&self, direction: Point) -> Self {
-        let mut updated_grid:HashMap<Point, Entity> = HashMap::new();
+    fn tilt(&self, direction: Direction) -> Self {
+        let mut updated_grid = self.grid.clone();
+        let size = self.grid.size();
 
-
-        let (y_direction, y_start, y_end) = if direction == SOUTH { (1, 0, self.total_size.y) } else { (-1, self.total_size.y - 1, -1)};
-        let (x_direction, x_start, x_end) = if direction == WEST { (1, 0, self.total_size.x) } else { (-1, self.total_size.x - 1, -1)};
+        // Work from the side being tilted towards, so every sphere slides over cells that have already settled
+        let (y_direction, y_start, y_end) = if direction == Direction::South { (1, 0, size.y) } else { (-1, size.y - 1, -1)};
+        let (x_direction, x_start, x_end) = if direction == Direction::West { (1, 0, size.x) } else { (-1, size.x - 1, -1)};
 
         let mut y_index = y_start;
         while y_index != y_end {
             let mut x_index = x_start;
             while x_index != x_end {
-                let current_point = Point::new(x_index, y_index);
-                let current_entity = self.grid.get(&current_point).unwrap();
-
-                if !matches!(current_entity, Entity::Sphere) {
-                    // No Movement
-                    updated_grid.insert(current_point, *current_entity);
-                    x_index += x_direction;
-                    continue;
-                }
+                let mut current_point = Point::new(x_index, y_index);
 
-                let mut destination = current_point + direction;
-                updated_grid.insert(current_point, Entity::Empty);
-                if updated_grid.contains_key(&destination) && matches!(updated_grid.get(&destination).unwrap(), Entity::Empty) {
-                    while updated_grid.contains_key(&destination) && matches!(updated_grid.get(&destination).unwrap(), Entity::Empty) {
-                        updated_grid.remove(&destination);
-                        updated_grid.insert(destination, *current_entity);
-                        updated_grid.insert(destination - direction, Entity::Empty);
-                        destination = destination + direction;
+                if matches!(updated_grid[current_point], Entity::Sphere) {
+                    while updated_grid.get(current_point + direction) == Some(&Entity::Empty) {
+                        updated_grid[current_point] = Entity::Empty;
+                        current_point = current_point + direction;
+                        updated_grid[current_point] = Entity::Sphere;
                     }
                 }
-                else {
-                    updated_grid.insert(current_point, *current_entity);
-                }
 
                 x_index += x_direction;
             }
@@ -140,29 +133,19 @@ impl Board {
             y_index += y_direction;
         }
 
-        Board { grid: updated_grid, total_size: self.total_size }
+        Board { grid: updated_grid }
     }
 
 
     fn get_board_load(&self) -> i32 {
-        let mut running_total = 0;
-        for y in (0..self.total_size.y).rev() {
-            for x in 0..self.total_size.x {
-                let entity = self.grid.get(&Point::new(x,y)).unwrap();
-                if matches!(entity, Entity::Sphere) {
-                    running_total += y + 1
-                }
-            }
-        }
-
-        running_total
+        weight_grid(&self.grid)
     }
 
     fn spin_cycle(&self) -> Self {
-        let mut updated = self.tilt(NORTH)
-            .tilt(WEST)
-            .tilt(SOUTH)
-            .tilt(EAST);
+        let mut updated = self.tilt(Direction::North)
+            .tilt(Direction::West)
+            .tilt(Direction::South)
+            .tilt(Direction::East);
 
 
         updated
@@ -172,7 +155,7 @@ impl Board {
 #[cfg(test)]
 mod tests {
     use crate::days::day_14::Board;
-    use crate::domain::point::NORTH;
+    use crate::domain::direction::Direction;
 
     #[test]
     fn can_get_board_load() {
@@ -187,8 +170,8 @@ O.#..O.#.#
 #....###..
 #OO..#...."#;
 
-        let board = Board::parse(input.to_string());
-        let tilted = board.tilt(NORTH);
+        let board = Board::parse(input).unwrap();
+        let tilted = board.tilt(Direction::North);
 
         assert_eq!(tilted.get_board_load(), 136);
     }
@@ -206,7 +189,7 @@ O.#..O.#.#
 #....###..
 #OO..#...."#;
 
-        let board = Board::parse(input.to_string());
+        let board = Board::parse(input).unwrap();
         let spun = board.spin_cycle();
 
         spun.print();
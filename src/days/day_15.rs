@@ -1,29 +1,61 @@
 use std::collections::BTreeMap;
-use std::fmt::Display;
-use crate::tools::parse_numbers;
+use crate::tools::parse_number;
 use indexmap::{indexmap, IndexMap};
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
 
-pub fn part_one(input: String) -> impl Display {
-    let result = input.replace("\n", "").split(",").map(|line| trust_the_process(line)).sum::<u32>();
+pub fn parse(input: &str) -> Result<Vec<Step>, ParseError> {
+    let steps:Vec<Vec<Step>> = parse_lines(input, |line| line.split(",").filter(|step| !step.is_empty()).map(|step| Step::parse(line, step)).collect())?;
 
-    result
+    Ok(steps.into_iter().flatten().collect())
 }
 
-pub fn part_two(input: String) -> impl Display {
-    holiday_ascii_string_helper_manual_arrangement_procedure(input)
+pub fn part_one(input: &Vec<Step>) -> Answer {
+    let result = input.iter().map(|step| trust_the_process(&step.text)).sum::<u32>();
+
+    result.into()
+}
+
+pub fn part_two(input: &Vec<Step>) -> Answer {
+    holiday_ascii_string_helper_manual_arrangement_procedure(input).into()
 }
 
-fn holiday_ascii_string_helper_manual_arrangement_procedure(input: String) -> u32 {
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<Step>;
+
+    fn day(&self) -> u32 {
+        15
+    }
+
+    fn title(&self) -> &'static str {
+        "Lens Library"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Step>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Vec<Step>) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Vec<Step>) -> Answer {
+        part_two(input)
+    }
+}
+
+pub fn holiday_ascii_string_helper_manual_arrangement_procedure(steps: &Vec<Step>) -> u32 {
     let mut box_map:BTreeMap<u32, IndexMap<String, i32>> = BTreeMap::new();
-    input.replace("\n", "").split(",").for_each(|line| process_instruction_line(line, &mut box_map));
+    steps.iter().for_each(|step| process_instruction_line(step, &mut box_map));
 
     let mut running_total = 0;
 
     for (key, entry) in box_map {
-        println!("{}", key);
         let mut slot_number = 1;
         for (label, focal) in entry {
-            println!("{} {}", label, focal);
             running_total += (key + 1) * (slot_number) * focal as u32;
             slot_number += 1;
         }
@@ -32,30 +64,25 @@ fn holiday_ascii_string_helper_manual_arrangement_procedure(input: String) -> u3
     running_total
 }
 
-fn process_instruction_line(instruction_line: &str, box_map: &mut BTreeMap<u32, IndexMap<String, i32>>) {
-    if instruction_line.contains("=") {
-        // Add Operation
-        let mut split = instruction_line.split("=");
-        let label = split.nth(0).unwrap().to_string();
-        let focal_len = parse_numbers(split.nth(0).unwrap()).unwrap().1;
-        let box_number = trust_the_process(&label);
-
-        if let Some(lens_box) = box_map.get_mut(&box_number) {
-            lens_box.insert(label.clone(), focal_len);
-        }
-        else {
-            let mut focal_box:IndexMap<String, i32> = IndexMap::new();
-            focal_box.insert(label.clone(), focal_len);
-            box_map.insert(box_number, focal_box);
+fn process_instruction_line(step: &Step, box_map: &mut BTreeMap<u32, IndexMap<String, i32>>) {
+    let label = &step.label;
+    let box_number = trust_the_process(label);
+
+    match step.operation {
+        Operation::Insert(focal_len) => {
+            if let Some(lens_box) = box_map.get_mut(&box_number) {
+                lens_box.insert(label.clone(), focal_len);
+            }
+            else {
+                let mut focal_box:IndexMap<String, i32> = IndexMap::new();
+                focal_box.insert(label.clone(), focal_len);
+                box_map.insert(box_number, focal_box);
+            }
         }
-    } else {
-        // Remove Operation
-        let mut split = instruction_line.split("-");
-        let label = split.nth(0).unwrap();
-        let box_number = trust_the_process(label);
-
-        if let Some(lens_box) = box_map.get_mut(&box_number) {
-            lens_box.shift_remove(label);
+        Operation::Remove => {
+            if let Some(lens_box) = box_map.get_mut(&box_number) {
+                lens_box.shift_remove(label);
+            }
         }
     }
 }
@@ -78,9 +105,35 @@ struct Lens {
     focal: i32
 }
 
+/// One step of the initialization sequence, kept as written since part one hashes it whole.
+pub struct Step {
+    pub text: String,
+    pub label: String,
+    pub operation: Operation
+}
+
+pub enum Operation {
+    Insert(i32),
+    Remove
+}
+
+impl Step {
+    pub fn parse(line: &str, step: &str) -> Result<Self, ParseError> {
+        let (label, operation) = if let Some((label, focal_len)) = step.split_once("=") {
+            (label, Operation::Insert(parse_number(line, focal_len)?))
+        } else if let Some(label) = step.strip_suffix("-") {
+            (label, Operation::Remove)
+        } else {
+            return Err(ParseError::at(line, step, format!("expected a label followed by - or =, found {:?}", step)));
+        };
+
+        Ok(Step { text: step.to_string(), label: label.to_string(), operation })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::days::day_15::{holiday_ascii_string_helper_manual_arrangement_procedure, trust_the_process};
+    use crate::days::day_15::{holiday_ascii_string_helper_manual_arrangement_procedure, parse, trust_the_process};
 
     #[test]
     fn can_process_hash() {
@@ -102,7 +155,7 @@ mod tests {
     fn hashmap() {
         let input = r#"rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7"#;
 
-        let result = holiday_ascii_string_helper_manual_arrangement_procedure(input.to_string());
+        let result = holiday_ascii_string_helper_manual_arrangement_procedure(&parse(input).unwrap());
 
         assert_eq!(result, 145);
     }
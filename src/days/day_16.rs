@@ -1,46 +1,77 @@
-use std::collections::{HashMap, HashSet};
-use std::fmt::Display;
-use crate::domain::point::{EAST, NORTH, Point, SOUTH, WEST};
+use std::collections::HashSet;
+use crate::domain::direction::Direction;
+use crate::domain::grid::Grid;
+use crate::domain::point::Point;
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(input, "., /, \\, | or -", |node| "./\\|-".contains(node).then_some(node))
+}
 
-pub fn part_one(input: String) -> impl Display {
+pub fn part_one(input: &Grid<char>) -> Answer {
+    process_light_beams_part_1(input).into()
+}
 
-    process_light_beams_part_1(input)
+pub fn part_two(input: &Grid<char>) -> Answer {
+    process_light_beams_part_2(input).into()
 }
 
-pub fn part_two(input: String) -> impl Display {
-    process_light_beams_part_2(input)
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Grid<char>;
+
+    fn day(&self) -> u32 {
+        16
+    }
+
+    fn title(&self) -> &'static str {
+        "The Floor Will Be Lava"
+    }
+
+    fn parse(&self, input: &str) -> Result<Grid<char>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Grid<char>) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Grid<char>) -> Answer {
+        part_two(input)
+    }
 }
 
 
-fn process_light_beams_part_1(input: String) -> usize {
-    let grid = Grid::parse(input);
-    test_configuration(&grid, LightBeam::new(Point::new(-1, grid.total_size.y - 1), Point::new(1,0)))
+pub fn process_light_beams_part_1(grid: &Grid<char>) -> usize {
+    test_configuration(grid, LightBeam::new(Point::new(-1, grid.size().y - 1), Direction::East))
 }
 
-fn process_light_beams_part_2(input: String) -> usize {
-    let grid = Grid::parse(input);
+pub fn process_light_beams_part_2(grid: &Grid<char>) -> usize {
     let mut possible_configurations:Vec<LightBeam> = Vec::new();
 
-    for x in 0..grid.total_size.x {
-        possible_configurations.push(LightBeam::new(Point::new(x, grid.total_size.y), SOUTH));
-        possible_configurations.push(LightBeam::new(Point::new(x, -1), NORTH));
+    for x in 0..grid.size().x {
+        possible_configurations.push(LightBeam::new(Point::new(x, grid.size().y), Direction::South));
+        possible_configurations.push(LightBeam::new(Point::new(x, -1), Direction::North));
     }
 
-    for y in 0..grid.total_size.y {
-        possible_configurations.push(LightBeam::new(Point::new(-1, y), EAST));
-        possible_configurations.push(LightBeam::new(Point::new(grid.total_size.x, y), WEST));
+    for y in 0..grid.size().y {
+        possible_configurations.push(LightBeam::new(Point::new(-1, y), Direction::East));
+        possible_configurations.push(LightBeam::new(Point::new(grid.size().x, y), Direction::West));
     }
     let mut configuration_results:Vec<usize> = Vec::new();
 
     for possible_configuration in possible_configurations {
-        configuration_results.push(test_configuration(&grid, possible_configuration.clone()));
+        configuration_results.push(test_configuration(grid, possible_configuration.clone()));
     }
 
     *configuration_results.iter().max().unwrap()
 }
 
 
-fn test_configuration(grid: &Grid, initial_beam: LightBeam) -> usize {
+fn test_configuration(grid: &Grid<char>, initial_beam: LightBeam) -> usize {
     let mut light_beams:Vec<LightBeam> = Vec::new();
     light_beams.push(initial_beam.clone());
 
@@ -63,62 +94,30 @@ fn test_configuration(grid: &Grid, initial_beam: LightBeam) -> usize {
     energized_tiles.len()
 }
 
-fn tick(grid: &Grid, light_beams: Vec<LightBeam>, energized: &HashSet<LightBeam>) -> Vec<LightBeam> {
+fn tick(grid: &Grid<char>, light_beams: Vec<LightBeam>, energized: &HashSet<LightBeam>) -> Vec<LightBeam> {
     let mut updated_light_beams:Vec<LightBeam> = Vec::new();
 
     for light_beam in light_beams {
         let updated_position = light_beam.position + light_beam.velocity;
 
-        if let Some(tile) = grid.nodes.get(&updated_position) {
-            match &tile {
-                '/' => {
-                    if light_beam.velocity == EAST {
-                        updated_light_beams.push(LightBeam::new(updated_position, NORTH));
-                    }
-                    if light_beam.velocity == NORTH {
-                        updated_light_beams.push(LightBeam::new(updated_position, EAST));
-                    }
-                    if light_beam.velocity == WEST {
-                        updated_light_beams.push(LightBeam::new(updated_position, SOUTH));
-                    }
-                    if light_beam.velocity == SOUTH {
-                        updated_light_beams.push(LightBeam::new(updated_position, WEST));
-                    }
-                },
-                '\\' => {
-                    if light_beam.velocity == WEST {
-                        updated_light_beams.push(LightBeam::new(updated_position, NORTH));
-                    }
-                    if light_beam.velocity == NORTH {
-                        updated_light_beams.push(LightBeam::new(updated_position, WEST));
-                    }
-                    if light_beam.velocity == EAST {
-                        updated_light_beams.push(LightBeam::new(updated_position, SOUTH));
-                    }
-                    if light_beam.velocity == SOUTH {
-                        updated_light_beams.push(LightBeam::new(updated_position, EAST));
-                    }
+        if let Some(tile) = grid.get(updated_position) {
+            let direction = light_beam.velocity;
+            match tile {
+                // Mirrors turn beams a quarter turn, which way depending on whether they hit side on
+                '/' if direction.is_horizontal() => updated_light_beams.push(LightBeam::new(updated_position, direction.turn_left())),
+                '/' => updated_light_beams.push(LightBeam::new(updated_position, direction.turn_right())),
+                '\\' if direction.is_horizontal() => updated_light_beams.push(LightBeam::new(updated_position, direction.turn_right())),
+                '\\' => updated_light_beams.push(LightBeam::new(updated_position, direction.turn_left())),
+                '|' if direction.is_horizontal() => {
+                    updated_light_beams.push(LightBeam::new(updated_position, Direction::North));
+                    updated_light_beams.push(LightBeam::new(updated_position, Direction::South));
                 },
-                '|' => {
-                    if light_beam.velocity == EAST || light_beam.velocity == WEST {
-                        updated_light_beams.push(LightBeam::new(updated_position, NORTH));
-                        updated_light_beams.push(LightBeam::new(updated_position, SOUTH));
-                    }
-                    else {
-                        updated_light_beams.push(LightBeam::new(updated_position, light_beam.velocity));
-                    }
+                '-' if !direction.is_horizontal() => {
+                    updated_light_beams.push(LightBeam::new(updated_position, Direction::East));
+                    updated_light_beams.push(LightBeam::new(updated_position, Direction::West));
                 },
-                '-' => {
-                    if light_beam.velocity == NORTH || light_beam.velocity == SOUTH {
-                        updated_light_beams.push(LightBeam::new(updated_position, EAST));
-                        updated_light_beams.push(LightBeam::new(updated_position, WEST));
-                    }
-                    else {
-                        updated_light_beams.push(LightBeam::new(updated_position, light_beam.velocity));
-                    }
-                }
                 _ => {
-                    updated_light_beams.push(LightBeam::new(updated_position, light_beam.velocity));
+                    updated_light_beams.push(LightBeam::new(updated_position, direction));
                 }
             }
         }
@@ -135,52 +134,24 @@ fn tick(grid: &Grid, light_beams: Vec<LightBeam>, energized: &HashSet<LightBeam>
 }
 
 
-struct Grid {
-    nodes: HashMap<Point, char>,
-    total_size: Point
-}
-
-impl Grid {
-    fn parse(input: String) -> Self {
-        let mut y_index = (input.lines().count() as i32);
-        let mut map:HashMap<Point, char> = HashMap::new();
-        let mut total_size:Point = Point::parse(input.lines().nth(0).unwrap().chars().count(), y_index as usize);
-
-        y_index -= 1;
-        for (y, line) in input.lines().enumerate() {
-            for (x, node) in line.chars().enumerate() {
-
-                map.insert(Point::new(x as i32, y_index as i32), node);
-            }
-            y_index = y_index - 1;
-
+fn print_grid_with_energy(grid: &Grid<char>, energized: &HashSet<Point>) {
+    let mut marked = grid.clone();
+    for point in energized {
+        if let Some(tile) = marked.get_mut(*point) {
+            *tile = '#';
         }
-
-        Grid { nodes: map, total_size }
     }
 
-    fn print_grid_with_energy(&self, energized: &HashSet<Point>) {
-        for y in (0..self.total_size.y).rev() {
-            for x in 0..self.total_size.x {
-                if energized.contains(&Point::new(x,y)) {
-                    print!("#");
-                }
-                else {
-                    print!("{}", self.nodes.get(&Point::new(x, y)).unwrap());
-                }
-            }
-            println!("");
-        }
-    }
+    println!("{}", marked);
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
 struct LightBeam {
     position: Point,
-    velocity: Point
+    velocity: Direction
 }
 impl LightBeam {
-    fn new(position:Point, velocity: Point) -> Self {
+    fn new(position:Point, velocity: Direction) -> Self {
         LightBeam { position, velocity }
     }
 }
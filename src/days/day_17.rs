@@ -1,363 +1,298 @@
-use std::cell::Cell;
-use std::collections::{HashMap, HashSet};
-use std::fmt::Display;
-use crate::domain::point::{EAST, NORTH, Point, SOUTH, WEST};
-use priority_queue::{PriorityQueue};
-use pathfinding::{directed::dijkstra::dijkstra, matrix::Matrix};
+use crate::domain::direction::Direction;
+use crate::domain::grid::Grid;
+use crate::domain::point::Point;
+use crate::domain::search::{a_star, Graph, Route};
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
 
 
-pub fn part_one(input: String) -> impl Display {
-    using_module(&input, 1, 3)
+pub fn parse(input: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse(input, "a heat loss digit", |c| c.to_digit(10))
 }
 
-pub fn part_two(input: String) -> impl Display {
-    using_module(&input, 4, 10)
+pub fn part_one(input: &Grid<u32>) -> Answer {
+    using_module(input, &CrucibleRules::crucible()).into()
 }
 
-fn part_one_finder(input: String) -> u32 {
-    let mut grid = &mut Grid::parse(input);
-    path_find(Point::new(0, grid.total_size.y - 1), Point::new(grid.total_size.x - 1, 0), &mut grid) as u32
+pub fn part_two(input: &Grid<u32>) -> Answer {
+    using_module(input, &CrucibleRules::ultra_crucible()).into()
 }
 
-fn using_module(input: &str, min_move: usize, max_move: usize) -> u32 {
-    let grid = Matrix::from_rows(
-        input
-            .lines()
-            .map(|l| l.chars().filter_map(|c| c.to_digit(10))),
-    )
-        .unwrap();
-    dijkstra(
-        &((0, 0), (0, 0), 0),
-        |&(pos, (dr, dc), l)| {
-            let mut next = Vec::with_capacity(3);
-            let mut e = |dir, l| {
-                next.extend(
-                    &grid
-                        .move_in_direction(pos, dir)
-                        .map(|t| ((t, dir, l), grid[t])),
-                );
-            };
-            if l < max_move {
-                e((dr, dc), l + 1);
-            }
-            if l >= min_move {
-                e((-dc, -dr), 1);
-                e((dc, dr), 1);
-            } else if l == 0 {
-                e((1, 0), 1);
-                e((0, 1), 1);
-            }
-            next
-        },
-        |&(pos, _, l)| pos == (grid.rows - 1, grid.columns - 1) && l >= min_move,
-    )
-        .unwrap()
-        .1
-}
-
-fn breadth_first(start_point: Point, end_point: Point, grid: &mut Grid) -> i32 {
-    let mut frontier:PriorityQueue<Point, i32> = PriorityQueue::new();
-    let mut closed:Vec<Point> = Vec::new();
-    grid.map.get_mut(&start_point).unwrap().cost = 0;
+pub struct Day17;
 
-    let mut cost_map = HashMap::new();
-    frontier.push(start_point, 0);
-    while frontier.len() > 0 {
-        let popped = frontier.pop().unwrap();
+impl Solution for Day17 {
+    type Input = Grid<u32>;
 
-        let current_pos = popped.0;
-        //
-        if current_pos == end_point {
-            break;
-        }
-
-        let mut current_cost = -popped.1;
-        println!("{}", current_cost);
-        let current_copy;
-        {
-            let current = &mut grid.get_node(current_pos);
-            current_copy = current.clone();
-        }
-
-        closed.push(current_copy.position);
-
-        // if current_copy.prev.is_some() {
-        //     let mut direction_marker = &current_copy.clone();
-        //     let mut counter = 0;
-        //     let direction = current_copy.position - current_copy.prev.unwrap();
-        //     while direction_marker.prev.is_some() && counter <= 4 {
-        //         if direction != direction_marker.position - direction_marker.prev.unwrap() {
-        //             break;
-        //         }
-        //         direction_marker = grid.get_node(direction_marker.prev.unwrap());
-        //         counter += 1;
-        //     }
-        //
-        //     if counter > 2 {
-        //         println!("{} Culled", current_copy.position + direction);
-        //         neighbors.retain(|&f| f != current_copy.position + direction);
-        //     }
-        // }
-        let directions = [SOUTH, NORTH, EAST, WEST];
-
-        for direction in directions {
-            for i in 1..3 {
-                let next_node = Point::new(direction.x * 1, direction.y * i) + current_pos;
-                if next_node.x >= grid.total_size.x || next_node.x < 0 || next_node.y >= grid.total_size.y || next_node.y < 0 {
-                    continue;
-                }
-
-                let neighbor_copy;
-                {
-                    let neighbor = &mut grid.get_node(next_node);
-                    neighbor_copy = neighbor.clone();
-                }
-
-                // if closed.contains(&neighbor_copy.position) {
-                //     continue;
-                // }
-
-                current_cost += neighbor_copy.heat_loss;
-
-                if current_cost < *cost_map.get(&neighbor_copy.position).unwrap_or(&i32::MAX) {
-                    grid.update_node(current_cost, current_copy.position, neighbor_copy.position);
-                    grid.map.get_mut(&neighbor_copy.position).unwrap().cost = current_cost;
-                    cost_map.insert(neighbor_copy.position, current_cost);
-                    frontier.push(neighbor_copy.position, -current_cost);
-                }
-            }
-        }
+    fn day(&self) -> u32 {
+        17
     }
 
-    let mut current_goal = grid.get_node(end_point);
+    fn title(&self) -> &'static str {
+        "Clumsy Crucible"
+    }
 
-    let mut counter = 0;
-    let mut positions:Vec<Point> = Vec::new();
-    positions.push(current_goal.position);
-    while current_goal.prev.is_some() && counter < 200 {
-        println!("{}", current_goal.position);
-        current_goal = grid.get_node(current_goal.prev.unwrap());
-        positions.push(current_goal.position.clone());
-        counter = counter + current_goal.heat_loss;
+    fn parse(&self, input: &str) -> Result<Grid<u32>, ParseError> {
+        parse(input)
     }
 
+    fn part_one(&self, input: &Grid<u32>) -> Answer {
+        part_one(input)
+    }
 
-    grid.print_grid(&positions);
-    0
+    fn part_two(&self, input: &Grid<u32>) -> Answer {
+        part_two(input)
+    }
 }
 
+/// How a crucible is allowed to move through the city, and between where.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrucibleRules {
+    /// Blocks it has to go in a straight line before it can turn
+    pub min_move: usize,
+    /// Blocks it can go in a straight line before it has to turn
+    pub max_move: usize,
+    /// Whether it can turn round and go back the way it came, counted as a turn
+    pub can_reverse: bool,
+    /// Whether it has to have gone `min_move` blocks in a straight line to stop at the goal
+    pub must_stop_after_min_move: bool,
+    /// Where it sets off from, the top left block if None
+    pub start: Option<Point>,
+    /// Where it has to get to, the bottom right block if None
+    pub goal: Option<Point>
+}
 
+impl CrucibleRules {
+    pub fn new(min_move: usize, max_move: usize) -> Self {
+        CrucibleRules { min_move, max_move, can_reverse: false, must_stop_after_min_move: true, start: None, goal: None }
+    }
 
-fn path_find(start_point: Point, end_point: Point, grid: &mut Grid) -> i32 {
-    let mut frontier:PriorityQueue<(Point, Point), i32> = PriorityQueue::new();
-    let mut closed:Vec<Point> = Vec::new();
-    grid.map.get_mut(&start_point).unwrap().cost = 0;
-    let start = grid.get_node(start_point);
-    frontier.push((start.position, Point::new(0,0)), 0);
-
-    while frontier.len() > 0 {
-
-        let popped = frontier.pop().unwrap();
-        println!("{}", -popped.1);
-        let current_pos = popped.0.0;
-        let current_copy;
-        {
-            let current = &mut grid.get_node(current_pos);
-            current_copy = current.clone();
-        }
+    /// The rules for part one.
+    pub fn crucible() -> Self {
+        CrucibleRules::new(1, 3)
+    }
 
-        if current_pos == end_point {
-            println!("DID I FIND IT {}", -popped.1);
-        }
-        closed.push(current_copy.position);
-
-
-        //
-        // let mut neighbors = grid.get_neighbors(current_copy.position);
-        // check previous to see if valid direction
-        // If the previous two in the path are the same direction, then remvoe the neighbor thats in that direction.
-        // if current_copy.prev.is_some() {
-        //     let mut direction_marker = &current_copy.clone();
-        //     let mut counter = 0;
-        //     let direction = current_copy.position - current_copy.prev.unwrap();
-        //     while direction_marker.prev.is_some() && counter <= 4 {
-        //         if direction != direction_marker.position - direction_marker.prev.unwrap() {
-        //             break;
-        //         }
-        //         direction_marker = grid.get_node(direction_marker.prev.unwrap());
-        //         counter += 1;
-        //     }
-        //
-        //     if counter > 2 {
-        //         println!("{} Culled", current_copy.position + direction);
-        //         neighbors.retain(|&f| f != current_copy.position + direction);
-        //     }
-        // }
-
-
-
-        let directions = [SOUTH, NORTH, EAST, WEST];
-
-        for direction in directions {
-            if popped.0.1 == direction {
-                continue;
-            }
-            let mut current_cost = -popped.1;
-            for i in 1..=3 {
-                let next_node = Point::new(direction.x * i, direction.y * i) + current_pos;
-                if next_node.x >= grid.total_size.x || next_node.x < 0 || next_node.y >= grid.total_size.y || next_node.y < 0 {
-                    continue;
-                }
-
-                let neighbor_copy;
-                {
-                    let neighbor = &mut grid.get_node(next_node);
-                    neighbor_copy = neighbor.clone();
-                }
-
-                current_cost += neighbor_copy.heat_loss;
-
-                // if frontier.get(&neighbor_copy.position).is_some() && current_cost < neighbor_copy.cost {
-                //     //println!("{}", neighbor_copy.position);
-                //     frontier.remove(&neighbor_copy.position);
-                // }
-
-                if closed.contains(&neighbor_copy.position) && current_cost < neighbor_copy.cost {
-                    //println!("Closed contains {}", neighbor_copy.position);
-                    closed.retain(|&x| x != neighbor_copy.position);
-                }
-
-                if current_cost < neighbor_copy.cost {
-                    grid.update_node(current_cost, current_pos + Point::new(direction.x * (i-1), direction.y * (i-1)), neighbor_copy.position);
-                    grid.map.get_mut(&neighbor_copy.position).unwrap().cost = current_cost;
-
-                    let priority = current_cost;
-                    //println!("Adding to Frontier {} {}", neighbor_copy.position, priority);
-                    frontier.push((neighbor_copy.position, direction), -priority);
-                }
-            }
-        }
+    /// The rules for part two.
+    pub fn ultra_crucible() -> Self {
+        CrucibleRules::new(4, 10)
+    }
 
+    fn start(&self, grid: &Grid<u32>) -> Point {
+        self.start.unwrap_or(Point::new(0, grid.height() as i32 - 1))
     }
 
-    println!("Finish it up");
-    let mut current_goal = grid.get_node(end_point);
-
-    let mut counter = 0;
-    let mut positions:Vec<Point> = Vec::new();
-    positions.push(current_goal.position);
-    while current_goal.prev.is_some() && counter < 200 {
-        println!("{}", current_goal.position);
-        current_goal = grid.get_node(current_goal.prev.unwrap());
-        positions.push(current_goal.position.clone());
-        counter = counter + current_goal.heat_loss;
+    fn goal(&self, grid: &Grid<u32>) -> Point {
+        self.goal.unwrap_or(Point::new(grid.width() as i32 - 1, 0))
     }
+}
 
+pub fn using_module(grid: &Grid<u32>, rules: &CrucibleRules) -> Result<u32, String> {
+    crucible_route(grid, rules).map(|route| route.cost as u32).ok_or("The crucible can never reach the factory".to_string())
+}
 
-    grid.print_grid(&positions);
+/// The route losing the least heat from the start to the goal, or None if the rules never let
+/// the crucible get there.
+pub fn crucible_route(grid: &Grid<u32>, rules: &CrucibleRules) -> Option<Route<Crucible>> {
+    let cheapest_block = grid.iter().map(|(_, heat_loss)| *heat_loss as u64).min().unwrap_or(0);
+    let city = City { grid, rules, goal: rules.goal(grid), cheapest_block };
+    let start = (rules.start(grid), Direction::East, 0);
 
-    return 0;
+    a_star(&city, start, |&(pos, _, l)| pos == city.goal && (l >= rules.min_move || !rules.must_stop_after_min_move))
 }
 
-fn heuristic(a:Point, b:Point) -> i32 {
-    return (a.x - b.x).abs() + (a.y - b.y).abs();
+/// Each block the route sets off from in a new direction, and the direction it sets off in.
+pub fn direction_changes(route: &Route<Crucible>) -> Vec<(Point, Direction)> {
+    route.path.windows(2)
+        .filter(|pair| pair[0].2 == 0 || pair[0].1 != pair[1].1)
+        .map(|pair| (pair[0].0, pair[1].1))
+        .collect()
 }
 
+/// The heat map with an arrow on every block the route enters, as the puzzle draws it.
+pub fn draw_route(grid: &Grid<u32>, route: &Route<Crucible>) -> Grid<char> {
+    let mut drawing = grid.map(|heat_loss| char::from_digit(*heat_loss, 10).unwrap_or('?'));
+    for &(pos, direction, _) in route.path.iter().skip(1) {
+        drawing[pos] = match direction {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<'
+        };
+    }
 
-
-struct Grid {
-    map: HashMap<Point, GridNode>,
-    total_size: Point
+    drawing
 }
 
-impl Grid {
-    fn parse(input: String) -> Self {
-        let mut y_index = (input.lines().count() as i32);
-        let mut map:HashMap<Point, GridNode> = HashMap::new();
-        let mut total_size:Point = Point::parse(input.lines().nth(0).unwrap().chars().count(), y_index as usize);
+/// A crucible is somewhere, heading some way, having gone some blocks in a straight line.
+pub type Crucible = (Point, Direction, usize);
 
-        y_index -= 1;
-        for (y, line) in input.lines().enumerate() {
-            for (x, node) in line.chars().enumerate() {
+struct City<'a> {
+    grid: &'a Grid<u32>,
+    rules: &'a CrucibleRules,
+    goal: Point,
+    cheapest_block: u64
+}
 
-                map.insert(Point::new(x as i32, y_index as i32), GridNode { position: Point::new(x as i32, y_index as i32), heat_loss: node.to_digit(10).unwrap() as i32, cost: i32::MAX, prev: Option::None, closed: Cell::new(false) });
+impl Graph for City<'_> {
+    type Node = Crucible;
+
+    fn neighbors(&self, &(pos, direction, l): &Crucible) -> Vec<(Crucible, u64)> {
+        let mut next = Vec::with_capacity(3);
+        let mut e = |dir: Direction, l| {
+            let t = pos + dir;
+            next.extend(self.grid.get(t).map(|heat_loss| ((t, dir, l), *heat_loss as u64)));
+        };
+        // Still at the start, so free to set off any way
+        if l == 0 {
+            for dir in Direction::ALL {
+                e(dir, 1);
+            }
+            return next;
+        }
+        if l < self.rules.max_move {
+            e(direction, l + 1);
+        }
+        if l >= self.rules.min_move {
+            e(direction.turn_left(), 1);
+            e(direction.turn_right(), 1);
+            if self.rules.can_reverse {
+                e(direction.opposite(), 1);
             }
-            y_index = y_index - 1;
-
         }
+        next
+    }
 
-        Grid { map, total_size }
+    // There are at least as many blocks left to enter as the goal is away, each losing at least as
+    // much heat as the cheapest block. Blocks can lose none, so this can't just count them.
+    fn heuristic(&self, &(pos, _, _): &Crucible) -> u64 {
+        pos.manhattan_distance(self.goal) as u64 * self.cheapest_block
     }
+}
 
-    fn get_neighbors(&self, pos: Point) -> Vec<Point> {
-        let directions = [SOUTH, NORTH, EAST, WEST];
-        let mut neighbor_points:Vec<Point> = Vec::new();
-        directions.iter().for_each(|dir| {
-            if self.map.contains_key(&(*dir + pos)) {
-                neighbor_points.push(*dir + pos);
-            }
-        });
+#[cfg(test)]
+mod tests {
+    use crate::days::day_17::{crucible_route, CrucibleRules, direction_changes, draw_route, parse, using_module};
+    use crate::domain::direction::Direction;
+    use crate::domain::point::Point;
+
+    #[test]
+    fn can_find_least_heat_loss() {
+        let input = r#"2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533"#;
+
+        let grid = parse(input).unwrap();
+
+        assert_eq!(using_module(&grid, &CrucibleRules::crucible()), Ok(102));
+        assert_eq!(using_module(&grid, &CrucibleRules::ultra_crucible()), Ok(94));
+    }
 
-        return neighbor_points;
+    #[test]
+    fn blocks_losing_no_heat_are_free() {
+        let grid = parse("000005\n900100\n005100").unwrap();
+
+        assert_eq!(using_module(&grid, &CrucibleRules::crucible()), Ok(0));
     }
 
-    fn print_grid(&self, movement: &Vec<Point>) {
-        for y in (0..self.total_size.y).rev() {
-            for x in 0..self.total_size.x {
-                if movement.contains(&Point::new(x,y)) {
-                    print!("#");
-                }
-                else {
-                     //print!("{}", self.map.get(&Point::new(x, y)).unwrap().heat_loss);
-                    print!(".")
-                }
-            }
-            println!("");
-        }
+    #[test]
+    fn an_empty_city_is_unsolved() {
+        let grid = parse("").unwrap();
+
+        assert_eq!(using_module(&grid, &CrucibleRules::crucible()), Err("The crucible can never reach the factory".to_string()));
     }
 
-    fn get_node(&self, pos: Point) -> &GridNode {
-        return self.map.get(&pos).unwrap();
+    #[test]
+    fn can_draw_the_route() {
+        let input = r#"2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533"#;
+
+        let grid = parse(input).unwrap();
+        let route = crucible_route(&grid, &CrucibleRules::ultra_crucible()).unwrap();
+
+        assert_eq!(route.cost, 94);
+        assert_eq!(draw_route(&grid, &route).to_string(), r#"2>>>>>>>>1323
+32154535v5623
+32552456v4254
+34465858v5452
+45466578v>>>>
+143859879845v
+445787698776v
+363787797965v
+465496798688v
+456467998645v
+122468686556v
+254654888773v
+432267465553v"#);
+        assert_eq!(direction_changes(&route), vec![
+            (Point::new(0, 12), Direction::East),
+            (Point::new(8, 12), Direction::South),
+            (Point::new(8, 8), Direction::East),
+            (Point::new(12, 8), Direction::South)
+        ]);
     }
 
-    fn update_node(&mut self, cost:i32, prev: Point, pos: Point) {
-        self.map.get_mut(&pos).unwrap().cost = cost;
-        self.map.get_mut(&pos).unwrap().prev = Option::Some(prev);
+    #[test]
+    fn can_stop_short_of_min_move_if_the_rules_allow() {
+        let input = r#"111111111111
+999999999991
+999999999991
+999999999991
+999999999991"#;
+
+        let grid = parse(input).unwrap();
+        let mut rules = CrucibleRules::ultra_crucible();
+        assert_eq!(using_module(&grid, &rules), Ok(71));
+
+        // Ten along the top, the shortest drop it can make and one more block to the goal
+        rules.must_stop_after_min_move = false;
+        assert_eq!(using_module(&grid, &rules), Ok(47));
     }
-}
 
-#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord)]
-struct GridNode {
-    position: Point,
-    heat_loss: i32,
-    cost: i32,
-    prev: Option<Point>,
-    closed: Cell<bool>
-}
+    #[test]
+    fn can_turn_back_if_the_rules_allow() {
+        let grid = parse("1119").unwrap();
+        let mut rules = CrucibleRules::crucible();
+        rules.start = Some(Point::new(0, 0));
+        rules.goal = Some(Point::new(0, 0));
 
+        // With only one row there is no turning left or right, so no way out and back
+        assert_eq!(crucible_route(&grid, &rules), None);
 
-#[cfg(test)]
-mod tests {
-    use crate::days::day_17::part_one_finder;
-
-//     #[test]
-//     fn test_path_find() {
-//         let input = r#"2413432311323
-// 3215453535623
-// 3255245654254
-// 3446585845452
-// 4546657867536
-// 1438598798454
-// 4457876987766
-// 3637877979653
-// 4654967986887
-// 4564679986453
-// 1224686865563
-// 2546548887735
-// 4322674655533"#;
-//
-//         let result = part_one_finder(input.to_string());
-//
-//         assert_eq!(result, 102);
-//     }
-}
\ No newline at end of file
+        rules.can_reverse = true;
+        let route = crucible_route(&grid, &rules).unwrap();
+        assert_eq!(route.cost, 2);
+        assert_eq!(direction_changes(&route), vec![(Point::new(0, 0), Direction::East), (Point::new(1, 0), Direction::West)]);
+    }
+
+    #[test]
+    fn can_go_between_any_blocks() {
+        let grid = parse("191\n111").unwrap();
+        let mut rules = CrucibleRules::crucible();
+        rules.start = Some(Point::new(2, 0));
+        rules.goal = Some(Point::new(0, 1));
+
+        // Along the bottom and up rather than up and through the 9
+        assert_eq!(using_module(&grid, &rules), Ok(3));
+    }
+}
@@ -1,45 +1,74 @@
 use std::collections::HashMap;
-use std::fmt::Display;
 use itertools::{Itertools, Position};
-use crate::domain::point::{EAST, NORTH, Point, SOUTH, WEST};
-use crate::tools::parse_numbers;
+use crate::domain::direction::Direction;
+use crate::domain::point::{EAST, NORTH, Point, PointLarge, SOUTH, WEST};
+use crate::tools::parse_number;
 use std::collections::VecDeque;
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
+
+pub fn parse(input: &str) -> Result<DigPlan, ParseError> {
+    Ok(DigPlan {
+        instructions: parse_lines(input, Instruction::parse)?,
+        decoded_instructions: parse_lines(input, Instruction::parse_advanced_instruction)?
+    })
+}
+
+pub fn part_one(input: &DigPlan) -> Answer {
+    laced(&input.instructions).into()
+}
 
-pub fn part_one(input: String) -> impl Display {
-    let instructions:Vec<Instruction> = input.lines().map(|l| Instruction::parse(l)).collect();
-    laced(instructions)
+pub fn part_two(input: &DigPlan) -> Answer {
+    laced(&input.decoded_instructions).into()
 }
 
-pub fn part_two(input: String) -> impl Display {
-    let instructions:Vec<Instruction> = input.lines().map(|l| Instruction::parse_advanced_instruction(l)).collect();
-    laced(instructions)
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = DigPlan;
+
+    fn day(&self) -> u32 {
+        18
+    }
+
+    fn title(&self) -> &'static str {
+        "Lavaduct Lagoon"
+    }
+
+    fn parse(&self, input: &str) -> Result<DigPlan, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &DigPlan) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &DigPlan) -> Answer {
+        part_two(input)
+    }
 }
 
-fn laced(instructions:Vec<Instruction>) -> usize {
-    let mut current:(isize, isize) = (0,0);
-    let mut prev:(isize, isize) = (0,0);
+fn laced(instructions:&Vec<Instruction>) -> usize {
+    let mut current = PointLarge::new(0, 0);
+    let mut prev = PointLarge::new(0, 0);
 
-    let mut count: usize = 0;
-    let mut s: isize = 0;
+    let mut count: i64 = 0;
+    let mut s: i64 = 0;
 
     for instruction in instructions {
-        match instruction.direction {
-            NORTH  => current.0 -= instruction.amount as isize,
-            SOUTH => current.0 += instruction.amount as isize,
-            EAST => current.1 += instruction.amount as isize,
-            WEST => current.1 -= instruction.amount as isize,
-            _ => panic!("Help")
-        }
+        let amount = instruction.amount as i64;
+        current = current + instruction.direction.offset().cast().scale(amount);
 
-        s += current.0 * prev.1 - current.1 * prev.0;
-        count += instruction.amount as usize;
+        s += current.cross(prev);
+        count += amount;
         prev = current;
     }
-    (s.abs() as usize / 2 + count / 2 + 1)
+    (s.abs() / 2 + count / 2 + 1) as usize
 }
 
-fn dig_it_up(input: String, run_fill: bool, print_grid: bool) -> usize {
-    let instructions:Vec<Instruction> = input.lines().map(|l| Instruction::parse(l)).collect();
+pub fn dig_it_up(plan: &DigPlan, run_fill: bool, print_grid: bool) -> usize {
+    let instructions = &plan.instructions;
     let mut dig_map = DigMap { map: HashMap::new(), current_position: Point::new(0,0), vertices: vec![], edges: vec![]};
     instructions.iter().for_each(|inst| dig_map.process(inst));
 
@@ -56,8 +85,8 @@ fn dig_it_up(input: String, run_fill: bool, print_grid: bool) -> usize {
 
 }
 
-fn dig_it_up_advanced(input: String, run_fill: bool, print_grid: bool) -> usize {
-    let instructions:Vec<Instruction> = input.lines().map(|l| Instruction::parse_advanced_instruction(l)).collect();
+pub fn dig_it_up_advanced(plan: &DigPlan, run_fill: bool, print_grid: bool) -> usize {
+    let instructions = &plan.decoded_instructions;
     let mut dig_map = DigMap { map: HashMap::new(), current_position: Point::new(0,0), vertices: vec![], edges: vec![]};
     instructions.iter().for_each(|inst| dig_map.process(inst));
 
@@ -83,14 +112,14 @@ struct DigMap {
 impl DigMap {
     fn process(&mut self, instruction: &Instruction) {
         for i in 1..=instruction.amount {
-            self.current_position = self.current_position.clone() + instruction.direction;
+            self.current_position = self.current_position + instruction.direction;
             self.map.insert(self.current_position, instruction.color.to_string());
         }
         self.vertices.push(self.current_position);
     }
 
     fn process_advanced(&mut self, instruction: &Instruction) {
-        self.vertices.push(*self.vertices.last().unwrap() + instruction.direction.scale(instruction.amount));
+        self.vertices.push(*self.vertices.last().unwrap() + instruction.direction.offset().scale(instruction.amount));
     }
 
     fn create_edges(&mut self) {
@@ -261,7 +290,8 @@ impl DigMap {
         let min_y = self.vertices.iter().min_by_key(|p| p.y).unwrap().y;
         let max_y = self.vertices.iter().max_by_key(|p| p.y).unwrap().y;
 
-        for y in min_y..=max_y {
+        // Highest y first, so north is at the top
+        for y in (min_y..=max_y).rev() {
             for x in min_x..=max_x {
                 if let Some(val) = self.map.get(&Point::new(x,y)) {
                     print!("{}", ansi_hex_color::colored(val, "", "#"));
@@ -301,64 +331,79 @@ impl Edge {
     }
 }
 
-struct Instruction {
-    direction: Point,
-    amount: i32,
-    color: String
+// The same lines read two ways: as written for part one, and from the colour codes for part two
+pub struct DigPlan {
+    pub instructions: Vec<Instruction>,
+    pub decoded_instructions: Vec<Instruction>
+}
+
+pub struct Instruction {
+    pub direction: Direction,
+    pub amount: i32,
+    pub color: String
 }
 
 impl Instruction {
-    fn parse(input_line: &str) -> Self {
+    pub fn parse(input_line: &str) -> Result<Self, ParseError> {
         let mut split = input_line.split_whitespace();
-        let direction_str = split.nth(0).unwrap();
+        let direction_str = split.next().ok_or_else(|| ParseError::missing(input_line, "a direction"))?;
         let direction = match direction_str {
-            "D" => { SOUTH }
-            "U" => { NORTH }
-            "L" => { WEST}
-            "R" => { EAST }
-            &_ => panic!("not parsable")
+            "D" => { Direction::South }
+            "U" => { Direction::North }
+            "L" => { Direction::West }
+            "R" => { Direction::East }
+            &_ => return Err(ParseError::at(input_line, direction_str, format!("expected U, D, L or R, found {:?}", direction_str)))
         };
 
-        let amount = parse_numbers(split.nth(0).unwrap()).unwrap().1;
-        let color = split.nth(0).unwrap().replace("(", "").replace(")", "");
+        let amount = parse_number(input_line, split.next().ok_or_else(|| ParseError::missing(input_line, "a distance"))?)?;
+        let color = Instruction::colour_code(input_line)?;
 
-        Instruction { direction, amount, color: color.to_string() }
+        Ok(Instruction { direction, amount, color: format!("#{}", color) })
     }
 
-    fn parse_advanced_instruction(input_line: &str) -> Self {
-        let mut split = input_line.split_whitespace();
-        let hex_value = split.nth(2).unwrap().replace("(", "").replace(")", "");
-        let color = hex_value.to_string();
-
-
-        let distance:String = hex_value.replace("#", "").chars().take(5).collect();
-        let amount = i32::from_str_radix(&distance, 16).unwrap();
-        let direction_char = hex_value.replace("#", "").chars().skip(5).take(1).at_most_one().unwrap();
-        let direction = match direction_char.unwrap() {
-            '1' => { SOUTH }
-            '3' => { NORTH }
-            '2' => { WEST}
-            '0' => { EAST }
-            _ => panic!("not parsable")
+    // The first five hex digits of the colour are the distance, the last one the direction
+    pub fn parse_advanced_instruction(input_line: &str) -> Result<Self, ParseError> {
+        let hex_value = Instruction::colour_code(input_line)?;
+        let color = format!("#{}", hex_value);
+
+        let (distance, direction_char) = hex_value.split_at(5);
+        let amount = i32::from_str_radix(distance, 16)
+            .map_err(|_| ParseError::at(input_line, distance, format!("expected a hex distance, found {:?}", distance)))?;
+        let direction = match direction_char {
+            "1" => { Direction::South }
+            "3" => { Direction::North }
+            "2" => { Direction::West }
+            "0" => { Direction::East }
+            _ => return Err(ParseError::at(input_line, direction_char, format!("expected a direction from 0 to 3, found {:?}", direction_char)))
         };
 
-        Instruction { color, direction, amount }
+        Ok(Instruction { color, direction, amount })
+    }
+
+    // The six hex digits inside the "(#...)" at the end of the line
+    fn colour_code(input_line: &str) -> Result<&str, ParseError> {
+        let token = input_line.split_whitespace().nth(2).ok_or_else(|| ParseError::missing(input_line, "a colour code"))?;
+        token.strip_prefix("(#")
+            .and_then(|code| code.strip_suffix(")"))
+            .filter(|code| code.len() == 6 && code.is_ascii())
+            .ok_or_else(|| ParseError::at(input_line, token, format!("expected a colour code like (#70c710), found {:?}", token)))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::days::day_18::{dig_it_up, dig_it_up_advanced, Edge, Instruction};
-    use crate::domain::point::{Point, SOUTH};
+    use crate::domain::direction::Direction;
+    use crate::domain::point::Point;
 
     #[test]
     fn can_parse_advanced() {
         let input = r#"D 5 (#0dc571)"#;
 
-        let instruction = Instruction::parse_advanced_instruction(input);
+        let instruction = Instruction::parse_advanced_instruction(input).unwrap();
 
         assert_eq!(instruction.amount, 56407);
-        assert_eq!(instruction.direction, SOUTH);
+        assert_eq!(instruction.direction, Direction::South);
         assert_eq!(instruction.color, "#0dc571");
     }
 
@@ -382,4 +427,12 @@ mod tests {
 
         assert_eq!(result, false);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn unknown_directions_are_reported() {
+        let error = Instruction::parse("X 6 (#70c710)").err().unwrap();
+
+        assert_eq!(error.column, Some(1));
+        assert_eq!(error.message, "expected U, D, L or R, found \"X\"");
+    }
+}
@@ -1,24 +1,67 @@
 use std::collections::{HashMap, VecDeque};
-use std::fmt::Display;
 use std::ops::Range;
 use nom::bytes::complete::{tag, take, take_till, take_until};
 use nom::IResult;
-use crate::tools::parse_numbers;
+use crate::tools::parse_number;
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
 
-pub fn part_one(input: String) -> impl Display {
-    factory_line(input)
+pub fn parse(input: &str) -> Result<SortingSystem, ParseError> {
+    let (workflows, parts) = parse_information(input)?;
+
+    Ok(SortingSystem { workflows, parts })
+}
+
+pub fn part_one(input: &SortingSystem) -> Answer {
+    factory_line(input).into()
 }
 
-fn factory_line(input: String) -> i32 {
-    let mut accepted:Vec<Part> = vec![];
-    let mut rejected:Vec<Part> = vec![];
-    let (workflows, parts) = parse_information(input);
+pub fn part_two(input: &SortingSystem) -> Answer {
+    factory_line_2(input).into()
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = SortingSystem;
 
-    for part in parts {
+    fn day(&self) -> u32 {
+        19
+    }
+
+    fn title(&self) -> &'static str {
+        "Aplenty"
+    }
+
+    fn parse(&self, input: &str) -> Result<SortingSystem, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &SortingSystem) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &SortingSystem) -> Answer {
+        part_two(input)
+    }
+}
+
+pub struct SortingSystem {
+    pub workflows: HashMap<String, Workflow>,
+    pub parts: Vec<Part>
+}
+
+pub fn factory_line(system: &SortingSystem) -> i32 {
+    let mut accepted:Vec<&Part> = vec![];
+    let mut rejected:Vec<&Part> = vec![];
+    let workflows = &system.workflows;
+
+    for part in &system.parts {
         let mut active_workflow = workflows.get("in").unwrap();
 
         loop {
-            let destination = active_workflow.get_part_destination(&part);
+            let destination = active_workflow.get_part_destination(part);
 
             match destination.as_str() {
                 "A" => {
@@ -40,14 +83,14 @@ fn factory_line(input: String) -> i32 {
     accepted.iter().map(|part| part.sum()).sum::<i32>()
 }
 
-fn factory_line_2(input: String) -> u128 {
+pub fn factory_line_2(system: &SortingSystem) -> u128 {
     let mut accepted:Vec<TheoreticalPart> = vec![];
     let mut rejected:Vec<TheoreticalPart> = vec![];
     let mut part_queue:VecDeque<(TheoreticalPart, String)> = VecDeque::new();
 
     part_queue.push_back((TheoreticalPart::blank(), "in".to_string()));
 
-    let (workflows, _) = parse_information(input);
+    let workflows = &system.workflows;
 
     while part_queue.len() > 0 {
         let (next_part, dest) = part_queue.pop_front().unwrap();
@@ -64,18 +107,15 @@ fn factory_line_2(input: String) -> u128 {
         let workflow = workflows.get(dest.as_str()).unwrap();
         let results = workflow.process_theoretical_part(&next_part);
 
-        println!("{}", results.len());
         for result in results {
             part_queue.push_back(result);
         }
 
     }
 
-    println!("{}", accepted.len());
     let mut max_value:u128 = 0;
 
     for part in &accepted {
-        part.print();
         max_value += part.get_combos();
         //println!("{}", part.get_combos());
     }
@@ -83,43 +123,46 @@ fn factory_line_2(input: String) -> u128 {
     max_value
 }
 
-fn parse_information(input: String) -> (HashMap<String, Workflow>, Vec<Part>) {
+fn parse_information(input: &str) -> Result<(HashMap<String, Workflow>, Vec<Part>), ParseError> {
     let mut split = input.split_terminator("\n\n");
-    let workflow_lines = split.nth(0).unwrap().lines();
-    let part_lines = split.nth(0).unwrap().lines();
+    let workflow_block = split.nth(0).unwrap_or_default();
+    let part_block = split.nth(0).ok_or_else(|| ParseError::new("expected the part ratings after a blank line"))?;
 
     let mut workflow_map:HashMap<String, Workflow> = HashMap::new();
-    workflow_lines.for_each(|line| {
-        let workflow = Workflow::parse(line).unwrap().1;
-
+    for workflow in parse_lines(workflow_block, Workflow::parse)? {
         workflow_map.insert(workflow.name.clone(), workflow);
-    });
-    let parts = part_lines.map(|line| Part::parse(line)).collect();
-
-    return (workflow_map,parts);
-}
+    }
+    if !workflow_map.contains_key("in") {
+        return Err(ParseError::new("there is no workflow named in to start from"));
+    }
+    let parts = parse_lines(part_block, Part::parse).map_err(|error| error.within(input, part_block))?;
 
-pub fn part_two(input: String) -> impl Display {
-    factory_line_2(input)
+    return Ok((workflow_map,parts));
 }
 
-struct Workflow {
-    name: String,
-    rules: Vec<WorkflowRule>
+pub struct Workflow {
+    pub name: String,
+    pub rules: Vec<WorkflowRule>
 }
 
 impl Workflow {
-    fn parse(input_line: &str) -> IResult<&str, Self> {
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let (_, (name, rules_string)) = Workflow::split(line).map_err(|error| ParseError::nom(line, error))?;
+
+        let rules = rules_string.split(",").map(|rule| WorkflowRule::parse(line, rule)).collect::<Result<_, _>>()?;
+
+        Ok(Workflow { name: name.to_string(), rules })
+    }
+
+    fn split(input_line: &str) -> IResult<&str, (&str, &str)> {
         let (input_line, name) = take_until("{")(input_line)?;
         let (input_line, _) = tag("{")(input_line)?;
         let (input_line, rules_string) = take_until("}")(input_line)?;
 
-        let rules = rules_string.split(",").map(|rule| WorkflowRule::parse(rule).unwrap().1).collect();
-
-        Ok((input_line, Workflow { name: name.to_string(), rules }))
+        Ok((input_line, (name, rules_string)))
     }
 
-    fn get_part_destination(&self, part: &Part) -> String {
+    pub fn get_part_destination(&self, part: &Part) -> String {
         for rule in &self.rules {
             if rule.can_apply(&part) {
                 return rule.destination.clone();
@@ -136,11 +179,8 @@ impl Workflow {
                 if rule.can_apply_in_theory(&next_part) {
                     if rule.requirement.is_some() {
                         let updated =  next_part.split_part(rule.requirement.unwrap());
-                        println!("{} {} {}", rule.requirement.unwrap().part_id, rule.requirement.unwrap().operator, rule.requirement.unwrap().part_req);
-                        updated[0].print();
                         results.push((updated[0].clone(), rule.destination.to_string()));
                         if updated.len() > 1 {
-                            updated[1].print();
                             next_part = updated[1].clone();
                         }
                     }
@@ -228,47 +268,64 @@ impl TheoreticalPart {
     }
 }
 
-struct Part {
-    values: HashMap<char, i32>
+pub struct Part {
+    pub values: HashMap<char, i32>
 }
 
 impl Part {
-    fn parse(input_line: &str) -> Self {
+    pub fn parse(input_line: &str) -> Result<Self, ParseError> {
         let mut values:HashMap<char, i32> = HashMap::new();
-        input_line.replace("{", "").replace("}", "").split(",").for_each(|split| {
-            let mut part_split = split.split("=");
-            let part_char = part_split.nth(0).unwrap();
-            let part_value = parse_numbers(part_split.nth(0).unwrap()).unwrap().1;
+        let ratings = input_line.strip_prefix("{")
+            .and_then(|ratings| ratings.strip_suffix("}"))
+            .ok_or_else(|| ParseError::at(input_line, input_line, "expected ratings between { and }"))?;
 
-            values.insert(part_char.chars().next().unwrap(), part_value);
-        });
+        for split in ratings.split(",") {
+            let (part_char, part_value) = split.split_once("=")
+                .ok_or_else(|| ParseError::at(input_line, split, format!("expected a rating like x=787, found {:?}", split)))?;
+            let category = rating_category(input_line, part_char)?;
+
+            values.insert(category, parse_number(input_line, part_value)?);
+        }
 
-        Part { values }
+        if let Some(category) = "xmas".chars().find(|category| !values.contains_key(category)) {
+            return Err(ParseError::at(input_line, input_line, format!("missing the {} rating", category)));
+        }
+
+        Ok(Part { values })
     }
 
-    fn sum(&self) -> i32 {
+    pub fn sum(&self) -> i32 {
         self.values.iter().map(|(id, val)| *val).sum::<i32>()
     }
 }
 
-struct WorkflowRule {
-    requirement: Option<Operation>,
-    destination: String
+pub struct WorkflowRule {
+    pub requirement: Option<Operation>,
+    pub destination: String
 }
 impl WorkflowRule {
-    fn parse(input_line: &str) -> IResult<&str, Self> {
+    pub fn parse(line: &str, input_line: &str) -> Result<Self, ParseError> {
         if(input_line.contains(":")){
-            let (input_line, id) = take_till(|f| f == '<' || f == '>')(input_line)?;
-            let (input_line, operator) = take(1usize)(input_line)?;
-            let (input_line, requirement) = take_until(":")(input_line)?;
-            let (input_line, _) = tag(":")(input_line)?;
-            let destination = input_line;
+            let (_, (id, operator, requirement, destination)) = WorkflowRule::split(input_line).map_err(|error| ParseError::nom(line, error))?;
+            let operator = match operator {
+                "<" | ">" => operator.chars().next().unwrap_or_default(),
+                _ => return Err(ParseError::at(line, operator, format!("expected < or >, found {:?}", operator)))
+            };
 
-            return Ok((input_line, WorkflowRule {destination: destination.to_string(), requirement: Some(Operation { operator: operator.chars().next().unwrap(), part_req: parse_numbers(requirement).unwrap().1, part_id: id.chars().next().unwrap() })}))
+            return Ok(WorkflowRule {destination: destination.to_string(), requirement: Some(Operation { operator, part_req: parse_number(line, requirement)?, part_id: rating_category(line, id)? })})
         }
 
 
-        Ok((input_line, WorkflowRule { destination: input_line.to_string(), requirement: None}))
+        Ok(WorkflowRule { destination: input_line.to_string(), requirement: None})
+    }
+
+    fn split(input_line: &str) -> IResult<&str, (&str, &str, &str, &str)> {
+        let (input_line, id) = take_till(|f| f == '<' || f == '>')(input_line)?;
+        let (input_line, operator) = take(1usize)(input_line)?;
+        let (input_line, requirement) = take_until(":")(input_line)?;
+        let (input_line, _) = tag(":")(input_line)?;
+
+        Ok((input_line, (id, operator, requirement, input_line)))
     }
     fn can_apply(&self, part: &Part) -> bool {
         if self.requirement.is_none() {
@@ -287,15 +344,22 @@ impl WorkflowRule {
     }
 
 }
+fn rating_category(line: &str, token: &str) -> Result<char, ParseError> {
+    match token {
+        "x" | "m" | "a" | "s" => Ok(token.chars().next().unwrap_or_default()),
+        _ => Err(ParseError::at(line, token, format!("expected one of x, m, a or s, found {:?}", token)))
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
-struct Operation {
-    part_id: char,
-    part_req: i32,
-    operator: char
+pub struct Operation {
+    pub part_id: char,
+    pub part_req: i32,
+    pub operator: char
 }
 
 impl Operation {
-    fn evaluate(&self, part: &Part) -> bool {
+    pub fn evaluate(&self, part: &Part) -> bool {
         if let Some(val) = part.values.get(&self.part_id) {
             return match self.operator {
                 '<' => val < &self.part_req,
@@ -325,13 +389,13 @@ impl Operation {
 #[cfg(test)]
 mod tests {
     use itertools::assert_equal;
-    use crate::days::day_19::{factory_line, factory_line_2, Operation, parse_information, Part, TheoreticalPart, Workflow};
+    use crate::days::day_19::{factory_line, factory_line_2, Operation, parse, parse_information, Part, TheoreticalPart, Workflow};
 
     #[test]
     fn can_parse_workflow() -> Result<(), String> {
         let input = r#"px{a<2006:qkq,m>2090:A,rfg}"#;
 
-        let workflow = Workflow::parse(input).unwrap().1;
+        let workflow = Workflow::parse(input).unwrap();
 
         assert_eq!(workflow.name, "px");
         assert_eq!(workflow.rules.len(), 3);
@@ -346,8 +410,8 @@ mod tests {
 
     #[test]
     fn can_get_workflow_destination_for_part() {
-        let workflow = Workflow::parse(r#"px{a<2006:qkq,m>2090:A,rfg}"#).unwrap().1;
-        let part = Part::parse(r#"{x=787,m=2655,a=1222,s=2876}"#);
+        let workflow = Workflow::parse(r#"px{a<2006:qkq,m>2090:A,rfg}"#).unwrap();
+        let part = Part::parse(r#"{x=787,m=2655,a=1222,s=2876}"#).unwrap();
 
         let destination = workflow.get_part_destination(&part);
 
@@ -358,7 +422,7 @@ mod tests {
     fn can_parse_part() {
         let input = r#"{x=787,m=2655,a=1222,s=2876}"#;
 
-        let part = Part::parse(input);
+        let part = Part::parse(input).unwrap();
 
         assert_eq!(part.values.len(), 4);
         assert_eq!(part.values.get(&'x').unwrap(), &787);
@@ -385,7 +449,7 @@ hdj{m>838:A,pv}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}"#;
 
-        let (workflows, parts) = parse_information(input.to_string());
+        let (workflows, parts) = parse_information(input).unwrap();
 
         assert_eq!(workflows.len(), 11);
         assert_eq!(parts.len(), 5);
@@ -411,7 +475,7 @@ hdj{m>838:A,pv}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}"#;
 
-        let result = factory_line(input.to_string());
+        let result = factory_line(&parse(input).unwrap());
 
         assert_eq!(result, 19114);
     }
@@ -430,7 +494,7 @@ hdj{m>838:A,pv}
 
     #[test]
     fn can_get_workflow_destination_for_theoritical_parts() {
-        let workflow = Workflow::parse(r#"px{a<2006:qkq,m>2090:A,rfg}"#).unwrap().1;
+        let workflow = Workflow::parse(r#"px{a<2006:qkq,m>2090:A,rfg}"#).unwrap();
         let part = TheoreticalPart::blank();
 
         let results = workflow.process_theoretical_part(&part);
@@ -444,7 +508,7 @@ hdj{m>838:A,pv}
 
     #[test]
     fn can_get_only_split_destination_for_theoritical_parts() {
-        let workflow = Workflow::parse(r#"in{s<1351:px,qqz}"#).unwrap().1;
+        let workflow = Workflow::parse(r#"in{s<1351:px,qqz}"#).unwrap();
         let part = TheoreticalPart::blank();
 
         let results = workflow.process_theoretical_part(&part);
@@ -477,7 +541,7 @@ hdj{m>838:A,pv}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}"#;
 
-        let result = factory_line_2(input.to_string());
+        let result = factory_line_2(&parse(input).unwrap());
 
         assert_eq!(result, 167409079868000);
     }
@@ -1,22 +1,59 @@
 use std::collections::{HashMap, VecDeque};
-use std::fmt::Display;
 use std::mem;
 use num::bigint::Sign;
 use num::integer::gcd;
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
 
-pub fn part_one(input: String) -> impl Display {
-    let mut signal_map = parse_information(input);
-    press_the_button(&mut signal_map, 1000)
+pub fn parse(input: &str) -> Result<ModuleConfiguration, ParseError> {
+    Ok(ModuleConfiguration { modules: parse_information(input)? })
 }
 
-pub fn part_two(input: String) -> impl Display {
-    let mut signal_map = parse_information(input);
-    press_until_signal_received(&mut signal_map)
+pub fn part_one(input: &ModuleConfiguration) -> Answer {
+    let mut signal_map = input.modules.clone();
+    press_the_button(&mut signal_map, 1000).into()
+}
+
+pub fn part_two(input: &ModuleConfiguration) -> Answer {
+    let mut signal_map = input.modules.clone();
+    press_until_signal_received(&mut signal_map).into()
+}
+
+// Modules keep their state as pulses go through, so each part presses the buttons on its own copy
+pub struct ModuleConfiguration {
+    pub modules: HashMap<String, Box<dyn Module>>
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = ModuleConfiguration;
+
+    fn day(&self) -> u32 {
+        20
+    }
+
+    fn title(&self) -> &'static str {
+        "Pulse Propagation"
+    }
+
+    fn parse(&self, input: &str) -> Result<ModuleConfiguration, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &ModuleConfiguration) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &ModuleConfiguration) -> Answer {
+        part_two(input)
+    }
 }
 
 
 
-fn press_the_button(signal_map: &mut HashMap<String, Box<dyn Module>>, iterations: i32) -> u64 {
+pub fn press_the_button(signal_map: &mut HashMap<String, Box<dyn Module>>, iterations: i32) -> u64 {
     let mut signal_queue:VecDeque<Signal> = VecDeque::new();
     let mut low_counter = 0;
     let mut high_counter = 0;
@@ -55,12 +92,37 @@ fn press_the_button(signal_map: &mut HashMap<String, Box<dyn Module>>, iteration
 
 
 
-fn press_until_signal_received(signal_map: &mut HashMap<String, Box<dyn Module>>) -> u64 {
+/// The conjunction that sends to rx, and the modules that feed it. rx only gets a low pulse once
+/// every one of them has sent the conjunction a high pulse during the same press. Errors unless
+/// rx hangs off a single conjunction with something feeding it, as the presses rely on that.
+pub fn rx_feeders(signal_map: &HashMap<String, Box<dyn Module>>) -> Result<(String, Vec<String>), String> {
+    let feeding_rx:Vec<&Box<dyn Module>> = signal_map.values().filter(|module| module.get_destinations().contains(&"rx".to_string())).collect();
+    let conjunction = match feeding_rx.as_slice() {
+        [] => return Err("No module sends pulses to rx".to_string()),
+        [module] if module.is_conjunction() => module.get_id(),
+        [module] => return Err(format!("rx is fed by {}, which is not a conjunction", module.get_id())),
+        modules => return Err(format!("rx is fed by {} modules rather than a single conjunction", modules.len()))
+    };
+
+    let senders:Vec<String> = signal_map.values()
+        .filter(|module| module.get_destinations().contains(&conjunction))
+        .map(|module| module.get_id())
+        .collect();
+    if senders.is_empty() {
+        return Err(format!("No module sends pulses to {}, the conjunction feeding rx", conjunction));
+    }
+
+    Ok((conjunction, senders))
+}
+
+pub fn press_until_signal_received(signal_map: &mut HashMap<String, Box<dyn Module>>) -> Result<u64, String> {
+    let (conjunction, senders) = rx_feeders(signal_map)?;
     let mut signal_queue:VecDeque<Signal> = VecDeque::new();
     let mut button_counter = 0;
     let mut rx_node_sender_map:HashMap<String, u64> = HashMap::new();
 
-    while rx_node_sender_map.len() < 4 {
+    // Each sender goes high on its own cycle, so rx fires on the first press all the cycles line up
+    while rx_node_sender_map.len() < senders.len() {
 
         button_counter += 1;
         signal_queue.push_back(Signal::Low("button".to_string(), "broadcaster".to_string()));
@@ -69,8 +131,8 @@ fn press_until_signal_received(signal_map: &mut HashMap<String, Box<dyn Module>>
 
             match &next_signal {
                 Signal::High(src, dest) => {
-                    if dest == &"zh".to_string() {
-                        rx_node_sender_map.insert(src.to_string(), button_counter);
+                    if dest == &conjunction {
+                        rx_node_sender_map.entry(src.to_string()).or_insert(button_counter);
                     }
                     if let Some(destination_module) = signal_map.get_mut(&dest.clone()) {
                         let new_signals = destination_module.receive_signal(next_signal.clone());
@@ -91,7 +153,7 @@ fn press_until_signal_received(signal_map: &mut HashMap<String, Box<dyn Module>>
         }
     }
     let value_set:Vec<u64> = rx_node_sender_map.values().map(|value| *value).collect();
-    find_lcm_of_set(value_set)
+    Ok(find_lcm_of_set(value_set))
 }
 
 fn find_lcm_of_set(numbers: Vec<u64>) -> u64 {
@@ -101,12 +163,11 @@ fn lcm(a: u64, b: u64) -> u64 {
     a / gcd(a, b) * b
 }
 
-fn parse_information(input: String) -> HashMap<String, Box<dyn Module>> {
+fn parse_information(input: &str) -> Result<HashMap<String, Box<dyn Module>>, ParseError> {
     let mut signal_map:HashMap<String, Box<dyn Module>> = HashMap::new();
-    input.lines().for_each(|line|
+    parse_lines(input, create_module)?.into_iter().for_each(|module|
         {
-            let module = create_module(line);
-            signal_map.insert(module.get_id(), create_module(line));
+            signal_map.insert(module.get_id(), module);
         }
     );
 
@@ -130,38 +191,53 @@ fn parse_information(input: String) -> HashMap<String, Box<dyn Module>> {
         module.initialize(destination_list.clone());
     });
 
-    signal_map
+    Ok(signal_map)
 }
 
-fn create_module(input_line: &str) -> Box<dyn Module> {
-    let mut split = input_line.split("->");
-    let module_id = split.nth(0).unwrap();
-    let destinations:Vec<String> = split.nth(0).unwrap().split(",").map(|m| m.trim().to_string()).collect();
+pub fn create_module(input_line: &str) -> Result<Box<dyn Module>, ParseError> {
+    let (module_id, destination_list) = input_line.split_once("->").ok_or_else(|| ParseError::missing(input_line, "-> followed by the destinations"))?;
+    let destinations:Vec<String> = destination_list.split(",").map(|m| m.trim().to_string()).collect();
+    if let Some(empty) = destination_list.split(",").find(|m| m.trim().is_empty()) {
+        return Err(ParseError::at(input_line, empty, "expected a destination module"));
+    }
 
     let mut chars = module_id.chars();
-    let module_type = chars.next().unwrap();
+    let module_type = chars.next().ok_or_else(|| ParseError::at(input_line, module_id, "expected a module name"))?;
     let module_identifier = chars.as_str().trim();
 
     return match module_type {
-        '&' => Box::new(ConjunctionModule { id: module_identifier.to_string(), last_pulse: Signal::Low("".to_string(), "".to_string()), connected_to: destinations, memory:HashMap::new() }),
-        '%' => Box::new(FlipFlopModule { id: module_identifier.to_string(), on: false, connected_to: destinations }),
-        _ => Box::new(BroadcasterModule { id: module_id.trim().to_string(), connected_to: destinations })
+        '&' => Ok(Box::new(ConjunctionModule { id: module_identifier.to_string(), last_pulse: Signal::Low("".to_string(), "".to_string()), connected_to: destinations, memory:HashMap::new() })),
+        '%' => Ok(Box::new(FlipFlopModule { id: module_identifier.to_string(), on: false, connected_to: destinations })),
+        _ if module_id.trim() == "broadcaster" => Ok(Box::new(BroadcasterModule { id: module_id.trim().to_string(), connected_to: destinations })),
+        _ => Err(ParseError::at(input_line, module_id, format!("expected % or & before {:?}, only the broadcaster has no type", module_id.trim())))
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, PartialOrd, Ord)]
-enum Signal {
+pub enum Signal {
     High(String, String),
     Low(String, String)
 }
 
-trait Module {
+pub trait Module: Send + Sync {
     fn receive_signal(&mut self, signal: Signal) -> Vec<Signal>;
     fn get_destinations(&self) -> Vec<String>;
     fn get_id(&self) -> String;
     fn initialize(&mut self, inputs: Vec<String>);
+    fn box_clone(&self) -> Box<dyn Module>;
+
+    fn is_conjunction(&self) -> bool {
+        false
+    }
+}
+
+impl Clone for Box<dyn Module> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
 }
 
+#[derive(Clone)]
 struct BroadcasterModule {
     id: String,
     connected_to: Vec<String>
@@ -182,12 +258,17 @@ impl Module for BroadcasterModule {
         return self.id.clone();
     }
 
+    fn box_clone(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+
     fn initialize(&mut self, inputs: Vec<String>) {
 
     }
 
 }
 
+#[derive(Clone)]
 struct ConjunctionModule {
     id: String,
     last_pulse: Signal,
@@ -223,13 +304,22 @@ impl Module for ConjunctionModule {
         return self.id.clone();
     }
 
+    fn box_clone(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+
     fn initialize(&mut self, inputs: Vec<String>) {
         for input in inputs {
             self.memory.insert(input.clone(), Signal::Low(input.clone(), self.id.clone()));
         }
     }
+
+    fn is_conjunction(&self) -> bool {
+        true
+    }
 }
 
+#[derive(Clone)]
 struct FlipFlopModule {
     id: String,
     on: bool,
@@ -261,6 +351,10 @@ impl Module for FlipFlopModule {
         return self.id.clone();
     }
 
+    fn box_clone(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+
     fn initialize(&mut self, inputs: Vec<String>) {
 
     }
@@ -268,22 +362,32 @@ impl Module for FlipFlopModule {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day_20::{create_module, parse_information, press_the_button, Signal};
+    use crate::days::day_20::{create_module, parse_information, press_the_button, press_until_signal_received, rx_feeders, Signal};
 
     #[test]
     fn can_create_module_from_line() {
         let input = r#"&zp -> px, gp, cl, bh, fn, ls, hs"#;
 
-        let result = create_module(input);
+        let result = create_module(input).unwrap();
 
         assert_eq!(result.get_destinations(), vec!["px", "gp", "cl", "bh", "fn", "ls", "hs"]);
         assert_eq!(result.get_id(), "zp");
     }
 
+    #[test]
+    fn modules_without_arrow_are_reported() {
+        let input = "broadcaster -> a\n%a inv, con";
+
+        let error = parse_information(input).err().unwrap();
+
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.message, "expected -> followed by the destinations");
+    }
+
     #[test]
     fn can_send_broadcast_signal() {
         let input = r#"broadcaster -> ls, bv, dc, br"#;
-        let mut module = create_module(input);
+        let mut module = create_module(input).unwrap();
 
         let expected = vec![Signal::Low("broadcaster".to_string(), "ls".to_string()),
                             Signal::Low("broadcaster".to_string(), "bv".to_string()),
@@ -299,7 +403,7 @@ mod tests {
     #[test]
     fn flip_flop_returns_nothing_on_high_signal() {
         let input = r#"%a -> b"#;
-        let mut module = create_module(input);
+        let mut module = create_module(input).unwrap();
 
         let result = module.receive_signal(Signal::High("".to_string(),"".to_string()));
         assert_eq!(result.len(), 0);
@@ -308,7 +412,7 @@ mod tests {
     #[test]
     fn flip_flop_flips_when_low() {
         let input = r#"%a -> b"#;
-        let mut module = create_module(input);
+        let mut module = create_module(input).unwrap();
 
         let result = module.receive_signal(Signal::Low("".to_string(),"".to_string()));
 
@@ -324,7 +428,7 @@ mod tests {
     #[test]
     fn conjuction_module_sends_signal() {
         let input = r#"&inv -> a"#;
-        let mut module = create_module(input);
+        let mut module = create_module(input).unwrap();
 
         let result = module.receive_signal(Signal::Low("c".to_string(), "".to_string()));
         assert_eq!(result.len(), 1);
@@ -341,7 +445,7 @@ mod tests {
     #[test]
     fn conjuction_module_remembers_input() {
         let input = r#"&inv -> a"#;
-        let mut module = create_module(input);
+        let mut module = create_module(input).unwrap();
 
         let result = module.receive_signal(Signal::High("c".to_string(), "".to_string()));
         assert_eq!(result.len(), 1);
@@ -358,7 +462,7 @@ mod tests {
     #[test]
     fn conjuction_module_remembers_initalized_input() {
         let input = r#"&con -> output"#;
-        let mut module = create_module(input);
+        let mut module = create_module(input).unwrap();
         module.initialize(vec!["a".to_string(), "b".to_string()]);
 
         let result = module.receive_signal(Signal::High("a".to_string(), "".to_string()));
@@ -380,7 +484,7 @@ mod tests {
 %c -> inv
 &inv -> a"#;
 
-        let mut map = parse_information(input.to_string());
+        let mut map = parse_information(input).unwrap();
 
         let result = press_the_button(&mut map, 1);
 
@@ -395,7 +499,7 @@ mod tests {
 %c -> inv
 &inv -> a"#;
 
-        let mut map = parse_information(input.to_string());
+        let mut map = parse_information(input).unwrap();
 
         let result = press_the_button(&mut map, 1000);
 
@@ -410,10 +514,74 @@ mod tests {
 %b -> con
 &con -> output"#;
 
-        let mut map = parse_information(input.to_string());
+        let mut map = parse_information(input).unwrap();
 
         let result = press_the_button(&mut map, 1000);
 
         assert_eq!(result, 11687500);
     }
+
+    #[test]
+    fn can_find_modules_feeding_rx() {
+        let input = r#"broadcaster -> a, b1
+%a -> na
+%b1 -> b2
+%b2 -> nb
+&na -> hub
+&nb -> hub
+&hub -> rx"#;
+
+        let map = parse_information(input).unwrap();
+
+        let (conjunction, mut senders) = rx_feeders(&map).unwrap();
+        senders.sort();
+
+        assert_eq!(conjunction, "hub");
+        assert_eq!(senders, vec!["na", "nb"]);
+    }
+
+    #[test]
+    fn presses_until_every_feeder_lines_up() {
+        let input = r#"broadcaster -> a, b1
+%a -> na
+%b1 -> b2
+%b2 -> nb
+&na -> hub
+&nb -> hub
+&hub -> rx"#;
+
+        let mut map = parse_information(input).unwrap();
+
+        assert_eq!(press_until_signal_received(&mut map), Ok(4));
+    }
+
+    #[test]
+    fn feeders_keep_their_first_press() {
+        // na goes high every 2 presses. b0 to b2 count to 5, when c resets them and nb goes high,
+        // so na has gone high twice before nb does at all
+        let input = r#"broadcaster -> a, b0
+%a -> na
+&na -> hub
+%b0 -> b1, c
+%b1 -> b2
+%b2 -> c
+&c -> b1, b0, nb
+&nb -> hub
+&hub -> rx"#;
+
+        let mut map = parse_information(input).unwrap();
+
+        assert_eq!(press_until_signal_received(&mut map), Ok(10));
+    }
+
+    #[test]
+    fn rx_has_to_hang_off_a_fed_conjunction() {
+        let no_rx = parse_information("broadcaster -> a\n%a -> output").unwrap();
+        let flip_flop = parse_information("broadcaster -> a\n%a -> rx").unwrap();
+        let unfed = parse_information("broadcaster -> a\n%a -> b\n&hub -> rx").unwrap();
+
+        assert_eq!(rx_feeders(&no_rx), Err("No module sends pulses to rx".to_string()));
+        assert_eq!(rx_feeders(&flip_flop), Err("rx is fed by a, which is not a conjunction".to_string()));
+        assert_eq!(rx_feeders(&unfed), Err("No module sends pulses to hub, the conjunction feeding rx".to_string()));
+    }
 }
\ No newline at end of file
@@ -1,185 +1,146 @@
-use std::collections::{HashMap, HashSet};
-use std::fmt::Display;
-use std::mem;
-use itertools::Itertools;
-use crate::domain::point::{EAST, NORTH, Point, SOUTH, WEST};
-use rustc_hash::FxHashSet;
-
-pub fn part_one(input: String) -> impl Display {
-    let grid = Grid::parse(input);
-    get_possible_end_spots(&grid, 64)
+use std::collections::HashMap;
+use crate::domain::direction::Direction;
+use crate::domain::grid::Grid;
+use crate::domain::point::Point;
+use crate::domain::search::{bfs_distances, Graph};
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+pub fn parse(input: &str) -> Result<Garden, ParseError> {
+    Garden::parse(input)
 }
 
-fn get_possible_end_spots(grid: &Grid, steps:i32) -> usize {
-    let possible_paths = path_finding_with_culling(&grid, steps);
+pub fn part_one(input: &Garden) -> Answer {
+    get_possible_end_spots(input, 64).into()
+}
 
-    let end_nodes:Vec<&Point> = possible_paths.iter().map(|path| path.path.last().unwrap()).collect::<HashSet<_>>().into_iter().collect();
+pub fn get_possible_end_spots(grid: &Garden, steps:i32) -> usize {
+    let distances = bfs_distances(grid, grid.start, steps as u64);
 
-    end_nodes.len()
+    count_end_spots(&distances, steps as u64)
 }
 
-pub fn part_two(input: String) -> impl Display {
-    let grid = Grid::parse(input);
-    quadratic(&grid)
+// Any plot reached in fewer steps can be reached in exactly `steps` by stepping back and forth, as
+// long as there is an even number of steps spare
+fn count_end_spots(distances: &HashMap<Point, u64>, steps: u64) -> usize {
+    distances.values().filter(|distance| **distance <= steps && **distance % 2 == steps % 2).count()
 }
 
-fn quadratic(grid: &Grid) -> u64 {
-    let [mut paths, mut next_paths] =
-        [
-            FxHashSet::<Point>::default(),
-            FxHashSet::<Point>::default()
-        ];
-    let mut step_set = vec![];
-    paths.insert(grid.start);
-    let mut steps_taken = 0;
-
-    while step_set.len() < 3 {
-        println!("Steps Taken {}", steps_taken);
-        steps_taken += 1;
-        for node in &paths {
-            let neighbors = grid.get_neighbors(*node);
-            for neighbor in neighbors {
-                next_paths.insert(neighbor);
-            }
+pub fn part_two(input: &Garden) -> Answer {
+    quadratic(input, 26501365).into()
+}
 
-        }
+pub struct Day21;
 
-        mem::swap(&mut paths, &mut next_paths);
+impl Solution for Day21 {
+    type Input = Garden;
 
-        if steps_taken % 131 == 65 {
-            step_set.push(paths.len());
-        }
+    fn day(&self) -> u32 {
+        21
     }
 
-    let n = 26501365 / 131;
-    let a = step_set[0];
-    let b = step_set[1];
-    let c = step_set[2];
-
-    let result = a + (b - a) * n + (a + c - 2 * b) * (n * (n - 1) / 2);
+    fn title(&self) -> &'static str {
+        "Step Counter"
+    }
 
-    result as u64
-}
+    fn parse(&self, input: &str) -> Result<Garden, ParseError> {
+        parse(input)
+    }
 
-fn get_end_point_count(paths: &Vec<PathFit>) -> u64 {
-    let end_nodes:Vec<&Point> = paths.iter().map(|path| path.path.last().unwrap()).collect::<HashSet<_>>().into_iter().collect();
+    fn part_one(&self, input: &Garden) -> Answer {
+        part_one(input)
+    }
 
-    end_nodes.len() as u64
+    fn part_two(&self, input: &Garden) -> Answer {
+        part_two(input)
+    }
 }
 
-fn path_finding_with_culling(grid: &Grid, steps: i32) -> Vec<PathFit> {
-    let mut paths = vec![PathFit { path: vec![grid.start]}];
-    let mut steps_left = steps;
-
-    while steps_left > 0 {
-        let mut next_paths:Vec<PathFit> = vec![];
-        steps_left -= 1;
-        for path in paths {
-            let current_node = path.get_last_step();
-            let neighbors = grid.get_neighbors(current_node);
-
-            for neighbor in neighbors {
-                let mut branch = path.clone();
-                branch.path.push(neighbor);
-                if !next_paths.iter().any(|next| next.get_last_step() == branch.get_last_step()) {
-                    next_paths.push(branch);
-                }
-            }
-
-        }
-
-        paths = next_paths;
+// The garden repeats every `period` steps in each direction. With the start in the middle and clear
+// rows and columns out to the edges, the count at `offset` steps, where the walk first reaches an
+// edge, then one and two periods later, pins down a quadratic in the number of whole periods walked.
+pub fn quadratic(garden: &Garden, steps: usize) -> Result<u64, String> {
+    let (period, offset) = garden.repeat()?;
+    if steps % period != offset {
+        return Err(format!("{} steps doesn't end at the edge of a garden, it has to be {} more than a multiple of {}", steps, offset, period));
     }
+    let sample_steps:Vec<usize> = (0..3).map(|periods| offset + periods * period).collect();
 
-    paths
-
-}
+    let distances = bfs_distances(garden, garden.start, sample_steps[2] as u64);
+    let step_set:Vec<usize> = sample_steps.iter().map(|steps| count_end_spots(&distances, *steps as u64)).collect();
 
-fn recursive_path_finding(path_fit: PathFit, grid: &Grid, current_point: Point, steps_left: i32) -> Vec<PathFit> {
-    let neighbors = grid.get_neighbors(current_point);
-    let mut paths:Vec<PathFit> = Vec::new();
+    let n = steps / period;
 
-    if steps_left == 0 {
-        return vec![path_fit]
+    if n < 3 {
+        return Ok(step_set[n] as u64);
     }
 
-    for neighbor in neighbors {
-        let steps_after_move = steps_left - 1;
-        let mut branch = path_fit.clone();
-        branch.path.push(neighbor);
+    let (a, b, c) = (step_set[0] as i128, step_set[1] as i128, step_set[2] as i128);
+    let n = n as i128;
 
-        paths.extend(recursive_path_finding(branch, grid, neighbor, steps_after_move));
-    }
+    let result = a + (b - a) * n + (a + c - 2 * b) * (n * (n - 1) / 2);
 
-    paths
+    if result < 0 {
+        return Err(format!("The plot counts {}, {} and {} extrapolate to a negative count", a, b, c));
+    }
+    u64::try_from(result).map_err(|_| format!("{} plots is too many to count in a u64", result))
 }
 
-#[derive(Debug, Clone)]
-struct PathFit {
-    path: Vec<Point>
+pub struct Garden {
+    pub plots: Grid<char>,
+    pub start: Point
 }
 
-impl PathFit {
-    fn get_last_step(&self) -> Point {
-        *self.path.last().unwrap()
+impl Garden {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let plots = Grid::parse(input, "., # or S", |node| ".#S".contains(node).then_some(node))?;
+        let start = plots.position(|node| *node == 'S').ok_or_else(|| ParseError::new("there is no starting plot S"))?;
+
+        Ok(Garden { plots, start })
     }
-}
 
-struct Grid {
-    nodes: HashMap<Point, char>,
-    total_size: Point,
-    start: Point
-}
+    /// How many steps it takes to cross the garden, and to get from the start to its edge, as long
+    /// as it is laid out for `quadratic` to work.
+    pub fn repeat(&self) -> Result<(usize, usize), String> {
+        let (width, height) = (self.plots.width(), self.plots.height());
+        if width != height || width % 2 == 0 {
+            return Err(format!("The garden is {}x{}, but has to be square with an odd side to have a middle", width, height));
+        }
 
-impl Grid {
-    fn parse(input: String) -> Self {
-        let mut y_index = (input.lines().count() as i32);
-        let mut map: HashMap<Point, char> = HashMap::new();
-        let mut total_size: Point = Point::parse(input.lines().nth(0).unwrap().chars().count(), y_index as usize);
-        let mut start = Point::new(0,0);
-        // y_index = (total_size.y - 1) / 2;
-        y_index -= 1;
-        for (y, line) in input.lines().enumerate() {
-            //let mut x_index = -((total_size.x - 1) / 2);
-            let mut x_index = 0;
-            for (x, node) in line.chars().enumerate() {
-                if node == 'S' {
-                    start = Point::new(x_index, y_index as i32);
-                    println!("{}", start);
-                }
-                map.insert(Point::new(x_index, y_index as i32), node);
-                x_index += 1;
-            }
-            y_index = y_index - 1;
+        let middle = (width / 2) as i32;
+        if self.start != Point::new(middle, middle) {
+            return Err("The start isn't in the middle of the garden".to_string());
+        }
+        if self.plots.points().any(|pos| (pos.x == middle || pos.y == middle) && self.plots[pos] == '#') {
+            return Err("The row and column through the start have to be clear of rocks".to_string());
         }
 
-        Grid { nodes: map, total_size, start }
+        Ok((width, width / 2))
     }
 
+    // The garden repeats forever in every direction, so positions outside it wrap around
     fn get_neighbors(&self, pos: Point) -> Vec<Point> {
-        let directions = [SOUTH, NORTH, EAST, WEST];
-        let mut neighbor_points:Vec<Point> = Vec::new();
-        let mut size = Point::new(self.total_size.x, self.total_size.y);
-        directions.iter().for_each(|dir| {
-            let p = *dir + pos;
-
-            let scaled_x = p.x.rem_euclid(size.x);
-            let scaled_y = p.y.rem_euclid(size.y);
-            let scaled_p = Point::new(scaled_x, scaled_y);
-
-            if let Some(node) = self.nodes.get(&scaled_p) {
-                if node != &'#' {
-                    neighbor_points.push(*dir + pos);
-                }
-            }
-        });
-
-        return neighbor_points;
+        let size = self.plots.size();
+
+        Direction::ALL.iter()
+            .map(|dir| pos + *dir)
+            .filter(|p| self.plots.get(Point::new(p.x.rem_euclid(size.x), p.y.rem_euclid(size.y))) != Some(&'#'))
+            .collect()
     }
 }
+
+impl Graph for Garden {
+    type Node = Point;
+
+    fn neighbors(&self, pos: &Point) -> Vec<(Point, u64)> {
+        self.get_neighbors(*pos).into_iter().map(|neighbor| (neighbor, 1)).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::days::day_21::{get_possible_end_spots, Grid};
+    use crate::days::day_21::{get_possible_end_spots, quadratic, Garden};
     use crate::domain::point::Point;
 
     #[test]
@@ -196,7 +157,7 @@ mod tests {
 .##..##.##.
 ..........."#;
 
-        let grid = Grid::parse(input.to_string());
+        let grid = Garden::parse(input).unwrap();
         let end_result = get_possible_end_spots(&grid, 6);
 
         assert_eq!(end_result, 16);
@@ -216,7 +177,7 @@ mod tests {
 .##..##.##.
 ..........."#;
 
-        let grid = Grid::parse(input.to_string());
+        let grid = Garden::parse(input).unwrap();
 
         let neighbors = grid.get_neighbors(Point::new(-11,0));
 
@@ -237,7 +198,7 @@ mod tests {
 .##..##.##.
 ..........."#;
 
-        let grid = Grid::parse(input.to_string());
+        let grid = Garden::parse(input).unwrap();
         let end_result = get_possible_end_spots(&grid, 100);
 
         assert_eq!(end_result, 6536);
@@ -257,10 +218,43 @@ mod tests {
 .##..##.##.
 ..........."#;
 
-        let grid = Grid::parse(input.to_string());
+        let grid = Garden::parse(input).unwrap();
         let end_result = get_possible_end_spots(&grid, 36);
 
         assert_eq!(end_result, 216);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn quadratic_matches_an_open_garden() {
+        let input = r#"...........
+...........
+...........
+...........
+...........
+.....S.....
+...........
+...........
+...........
+...........
+..........."#;
+
+        let garden = Garden::parse(input).unwrap();
+
+        // With nothing in the way every plot of the right parity within reach counts
+        assert_eq!(garden.repeat(), Ok((11, 5)));
+        assert_eq!(quadratic(&garden, 995), Ok(996 * 996));
+        assert_eq!(quadratic(&garden, 5), Ok(36));
+        assert!(quadratic(&garden, 1000).is_err());
+    }
+
+    #[test]
+    fn quadratic_needs_a_centred_start_on_clear_lines() {
+        let example = Garden::parse("...........\n.....###.#.\n.###.##..#.\n..#.#...#..\n....#.#....\n.##..S####.\n.##..#...#.\n.......##..\n.##.#.####.\n.##..##.##.\n...........").unwrap();
+        let off_centre = Garden::parse("S..\n...\n...").unwrap();
+        let not_square = Garden::parse("...\n.S.\n...\n...").unwrap();
+
+        assert_eq!(example.repeat(), Err("The row and column through the start have to be clear of rocks".to_string()));
+        assert_eq!(off_centre.repeat(), Err("The start isn't in the middle of the garden".to_string()));
+        assert!(quadratic(&not_square, 1).is_err());
+    }
+}
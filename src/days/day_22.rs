@@ -1,26 +1,32 @@
-#![feature(iter_array_chunks)]
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::{Display, Formatter, Write};
 use std::ops::Sub;
 use itertools::chain;
 use rustc_hash::FxHashSet;
 use crate::domain::vector3::Vector3;
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
 
-pub fn part_one(input: String) -> impl Display {
+pub fn parse(input: &str) -> Result<Vec<Brick>, ParseError> {
+    get_bricks(input)
+}
+
+pub fn part_one(input: &Vec<Brick>) -> Answer {
 
-    process_falling_bricks(World { grid: HashMap::new(), bricks: Vec::new()}, get_bricks(input))
+    process_falling_bricks(World { grid: HashMap::new(), bricks: Vec::new()}, input.clone()).into()
 }
 
-fn get_bricks(input: String) -> Vec<Brick> {
+fn get_bricks(input: &str) -> Result<Vec<Brick>, ParseError> {
     let mut counter_id = 0;
-    input.lines().map(|line|
+    parse_lines(input, |line|
         {
             counter_id += 1;
             Brick::parse(line, counter_id)
-        }).collect()
+        })
 }
 
-fn process_falling_bricks(mut world: World, mut bricks: Vec<Brick>) -> i32 {
+pub fn process_falling_bricks(mut world: World, mut bricks: Vec<Brick>) -> i32 {
     bricks.sort_by(|a,b| a.position.z.cmp(&b.position.z));
 
     let mut falling_bricks = bricks.clone();
@@ -32,17 +38,40 @@ fn process_falling_bricks(mut world: World, mut bricks: Vec<Brick>) -> i32 {
     // world.bricks.iter().for_each(|brick| println!("{}", brick));
 
     let valid_targets =  world.get_valid_disintegration_targets();
-    for brick in &valid_targets {
-        println!("{}", brick);
-    }
     valid_targets.len() as i32
 }
 
-pub fn part_two(input: String) -> impl Display {
-    process_bricks_and_chain_reaction(World { grid: HashMap::new(), bricks: Vec::new()}, get_bricks(input))
+pub fn part_two(input: &Vec<Brick>) -> Answer {
+    process_bricks_and_chain_reaction(World { grid: HashMap::new(), bricks: Vec::new()}, input.clone()).into()
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Vec<Brick>;
+
+    fn day(&self) -> u32 {
+        22
+    }
+
+    fn title(&self) -> &'static str {
+        "Sand Slabs"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Brick>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &Vec<Brick>) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Vec<Brick>) -> Answer {
+        part_two(input)
+    }
 }
 
-fn process_bricks_and_chain_reaction(mut world: World, mut bricks: Vec<Brick>) -> i32 {
+pub fn process_bricks_and_chain_reaction(mut world: World, mut bricks: Vec<Brick>) -> i32 {
     bricks.sort_by(|a,b| a.position.z.cmp(&b.position.z));
 
     let mut falling_bricks = bricks.clone();
@@ -54,7 +83,6 @@ fn process_bricks_and_chain_reaction(mut world: World, mut bricks: Vec<Brick>) -
     for brick in &world.bricks {
         // let chain_reaction_count = get_chain_reaction_recursive(&world, *brick, &HashSet::new()).len() - 1;
         let chain_reaction_count = chain_reaction_try_2(&world, brick);
-        println!("Brick {} - chains {}", brick.id, chain_reaction_count);
         total_chains += chain_reaction_count;
     }
 
@@ -150,9 +178,9 @@ fn check_overlap(brick_1: &Brick, brick_2: &Brick) -> bool {
     return overlap_x && overlap_y && overlap_z;
 }
 #[derive(Debug, Clone)]
-struct World {
-    grid: HashMap<Vector3, Brick>,
-    bricks: Vec<Brick>
+pub struct World {
+    pub grid: HashMap<Vector3, Brick>,
+    pub bricks: Vec<Brick>
 }
 
 impl World {
@@ -272,18 +300,19 @@ impl World {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
-struct Brick {
-    position: Vector3,
-    size: Vector3,
-    id: i32
+pub struct Brick {
+    pub position: Vector3,
+    pub size: Vector3,
+    pub id: i32
 }
 
 impl Brick {
-    fn parse(input_line:&str, id: i32) -> Self {
-        let mut split = input_line.split("~");
-        let [position1, position2] = split.map(|s| Vector3::parse(s)).next_chunk().unwrap();
+    pub fn parse(input_line:&str, id: i32) -> Result<Self, ParseError> {
+        let (start, end) = input_line.split_once("~").ok_or_else(|| ParseError::missing(input_line, "~ between the two ends of the brick"))?;
+        let position1 = Vector3::parse(input_line, start)?;
+        let position2 = Vector3::parse(input_line, end)?;
 
-        Brick { position: position1, size: position2 - position1, id }
+        Ok(Brick { position: position1, size: position2 - position1, id })
     }
 
     fn get_bounds(&self) -> (Vector3, Vector3) {
@@ -329,7 +358,7 @@ mod tests {
 0,1,6~2,1,6
 1,1,8~1,1,9"#;
 
-        let result = process_falling_bricks(World { grid: HashMap::new(), bricks: Vec::new()}, get_bricks(input.to_string()));
+        let result = process_falling_bricks(World { grid: HashMap::new(), bricks: Vec::new()}, get_bricks(input).unwrap());
 
         assert_eq!(result, 5);
     }
@@ -344,7 +373,7 @@ mod tests {
 0,1,6~2,1,6
 1,1,8~1,1,9"#;
 
-        let result = process_bricks_and_chain_reaction(World { grid: HashMap::new(), bricks: Vec::new()}, get_bricks(input.to_string()));
+        let result = process_bricks_and_chain_reaction(World { grid: HashMap::new(), bricks: Vec::new()}, get_bricks(input).unwrap());
 
         assert_eq!(result, 7);
     }
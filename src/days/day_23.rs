@@ -1,257 +1,171 @@
-use std::cmp;
-use std::collections::{HashMap, HashSet};
-use std::fmt::Display;
-use itertools::max;
-use priority_queue::PriorityQueue;
-use rustc_hash::FxHashMap;
-use crate::domain::point::{EAST, NORTH, Point, SOUTH, WEST};
-
-pub fn part_one(input: String) -> impl Display {
-    find_longest_path(input, false)
+use crate::domain::direction::Direction;
+use crate::domain::grid::Grid;
+use crate::domain::point::Point;
+use crate::domain::search::{Graph, longest_path, Route};
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+pub fn parse(input: &str) -> Result<TrailMap, ParseError> {
+    TrailMap::parse(input)
 }
 
-fn find_longest_path(input: String, part_2: bool) -> i32 {
-    let grid = Grid::parse(input);
-    grid.print_grid(&vec![]);
-
-    let max_path = iterative_path_finding(PathFit { path: HashSet::new() }, &grid, grid.start, part_2);
-
-    max_path
+pub fn part_one(input: &TrailMap) -> Answer {
+    find_longest_path(input, false).into()
 }
 
-fn find_a_star_path(input: String, part_2: bool) -> i32 {
-    let grid = Grid::parse(input);
-    grid.print_grid(&vec![]);
-
-    let path_length = a_star_path_finding(&grid, grid.start, part_2);
-
-    path_length
+pub fn find_longest_path(grid: &TrailMap, part_2: bool) -> Result<u64, String> {
+    JunctionGraph::build(grid, part_2).longest_path()
 }
 
+pub fn longest_route(map: &TrailMap, part_2: bool) -> Result<Route<Point>, String> {
+    let trails = Trails { map, climb_slopes: part_2 };
 
-pub fn part_two(input: String) -> impl Display {
-    find_longest_path(input, true)
+    longest_path(&trails, map.start, |pos| *pos == map.end).ok_or("There is no way from the start to the end".to_string())
 }
 
-fn try_number_4(grid: &Grid) -> i32 {
-    let mut stack: Vec<Point> = Vec::new();
-    stack.push(grid.start);
-    let mut cache_map:HashMap<Point, i32> = HashMap::new();
-    let mut dp = vec![vec![0; grid.total_size.y as usize]; grid.total_size.x as usize];
-    cache_map.insert(grid.start, 1);
-    let mut max_path = 0;
-    dp[grid.start.x as usize][grid.start.y as usize] = 1;
-
-    for x in 0..grid.total_size.x {
-        for y in 0..grid.total_size.y {
-            let current_point = Point::new(x, y);
-            if !grid.is_movable(&current_point) {
-                continue
-            }
-            println!("{}", current_point);
-            let neighbors = grid.get_neighbors_part2(current_point);
-            for neighbor in neighbors {
-                dp[neighbor.x as usize][neighbor.y as usize] = dp[neighbor.x as usize][neighbor.y as usize].max(dp[current_point.x as usize][current_point.y as usize] + 1);
-                max_path = max_path.max(dp[neighbor.x as usize][neighbor.y as usize]);
-            }
-        }
-    }
-    max_path + 1
+pub fn part_two(input: &TrailMap) -> Answer {
+    find_longest_path(input, true).into()
 }
 
-fn iterative_path_finding(path_fit: PathFit, grid: &Grid, start_point: Point, part_2: bool) -> i32 {
+pub struct Day23;
 
-    let mut graph:FxHashMap<Point, Vec<Point>> = FxHashMap::default();
+impl Solution for Day23 {
+    type Input = TrailMap;
 
-    for (key, _) in &grid.nodes {
-        let neighbors = match part_2 {
-            true => grid.get_neighbors_part2(*key),
-            false => grid.get_neighbors(*key)
-        };
-        graph.insert(*key, neighbors.clone());
+    fn day(&self) -> u32 {
+        23
     }
 
-    let mut stack: Vec<(PathFit, Point)> = Vec::new();
-    stack.push((path_fit, start_point));
-
-    let mut max_path:i32 = 0;
-    let mut counter:u128 = 0;
-    let mut solution_counter = 0;
-
-    while let Some((current_path_fit, current_point)) = stack.pop() {
-        counter += 1;
-
-        if counter % 100000 == 0 {
-            let max = stack.iter().map(|(p,_)| p.path.len()).max();
-            println!("Iterations: {} - Max: {} - Solutions: {} Max In progress: {} for {}", counter, max_path, solution_counter, max.unwrap_or(0), stack.len());
-        }
-
-        let neighbors = graph.get(&current_point).unwrap();
-
-        if current_path_fit.contains(&grid.end) {
-            solution_counter += 1;
-            max_path = max_path.max(current_path_fit.path.len() as i32);
-            continue;
-        }
-
-        for neighbor in neighbors {
-            if current_path_fit.path.contains(&neighbor) {
-                continue;
-            }
+    fn title(&self) -> &'static str {
+        "A Long Walk"
+    }
 
-            let mut branch = current_path_fit.clone();
-            branch.path.insert(*neighbor);
+    fn parse(&self, input: &str) -> Result<TrailMap, ParseError> {
+        parse(input)
+    }
 
-            stack.push((branch, *neighbor));
-        }
+    fn part_one(&self, input: &TrailMap) -> Answer {
+        part_one(input)
     }
 
-    max_path
+    fn part_two(&self, input: &TrailMap) -> Answer {
+        part_two(input)
+    }
 }
 
-// Im not sure A-Star is usable. How do you build towards the consensus?
-fn a_star_path_finding(grid: &Grid, start_point: Point, part_2: bool) -> i32 {
-    let mut frontier: PriorityQueue<Point, i32> = PriorityQueue::new();
-    let mut closed: Vec<Point> = Vec::new();
-    let mut cost_so_far: HashMap<Point, i32> = HashMap::new();
-    let mut came_from = HashMap::new();
-
-    frontier.push(start_point, 0);
-
-    came_from.insert(start_point, None);
-    cost_so_far.insert(start_point, 0);
-
-    while frontier.len() > 0 {
-        let popped = frontier.pop().unwrap();
-
-        let current_pos = popped.0;
-
-        closed.push(current_pos);
-
-        if current_pos == grid.end {
-            break;
-        }
+// Part two treats the slopes as ordinary path
+struct Trails<'a> {
+    map: &'a TrailMap,
+    climb_slopes: bool
+}
 
+impl Graph for Trails<'_> {
+    type Node = Point;
 
-        let neighbors = match part_2 {
-            true => grid.get_neighbors_part2(current_pos),
-            false => grid.get_neighbors(current_pos)
+    fn neighbors(&self, pos: &Point) -> Vec<(Point, u64)> {
+        let neighbors = match self.climb_slopes {
+            true => self.map.get_neighbors_part2(*pos),
+            false => self.map.get_neighbors(*pos)
         };
-        for neighbor in neighbors {
-
-            let cost = cost_so_far.get(&current_pos).unwrap() + 1;
 
-            if frontier.get(&neighbor).is_some() && cost > *cost_so_far.get(&neighbor).unwrap() {
-                frontier.remove(&neighbor);
-            }
-            //
-            // if closed.contains(&neighbor) && cost > *cost_so_far.get(&neighbor).unwrap()  {
-            //     closed.retain(|&x| x != neighbor);
-            // }
-
-            if !frontier.get(&neighbor).is_some() && !closed.contains(&neighbor) {
-                cost_so_far.insert(neighbor, cost);
-                let priority = cost + heuristic(neighbor, grid.end);
-                frontier.push(neighbor, priority);
-                came_from.insert(neighbor, Some(current_pos));
-            }
-        }
-    }
-    let mut path = Vec::new();
-    let mut current = grid.end;
-    while let Some(&Some(prev)) = came_from.get(&current) {
-        path.push(current);
-        current = prev;
+        neighbors.into_iter().map(|neighbor| (neighbor, 1)).collect()
     }
-    path.push(grid.start);
-    path.reverse();
-
-    grid.print_grid(&path);
-
-    return path.len() as i32
 }
 
-fn heuristic(a:Point, b:Point) -> i32 {
-    return (a.x - b.x).abs() + (a.y - b.y).abs();
+/// The trails squashed down to the start, the end and the places where paths branch, each joined
+/// to the ones it reaches without passing another by a corridor of some length.
+pub struct JunctionGraph {
+    pub junctions: Vec<Point>,
+    /// For each junction, the junctions down each corridor out of it and how long the corridor is
+    pub corridors: Vec<Vec<(usize, u64)>>,
+    pub start: usize,
+    pub end: usize
 }
 
-// Recurisve failed because it caused a stack overflow
-fn recursive_path_finding(path_fit: PathFit, grid: &Grid, current_point: Point) -> Vec<PathFit> {
-    let neighbors = grid.get_neighbors(current_point);
-    let mut paths: Vec<PathFit> = Vec::new();
+impl JunctionGraph {
+    pub fn build(map: &TrailMap, part_2: bool) -> Self {
+        let trails = Trails { map, climb_slopes: part_2 };
+        let mut junctions = vec![map.start, map.end];
+        junctions.extend(map.trails.points().filter(|pos| map.is_movable(pos) && map.get_neighbors_part2(*pos).len() > 2));
+
+        let corridors = junctions.iter()
+            .map(|junction| trails.neighbors(junction).into_iter()
+                .filter_map(|(first_step, _)| JunctionGraph::follow_corridor(&trails, &junctions, *junction, first_step))
+                .collect())
+            .collect();
 
-    if path_fit.contains(&grid.end) {
-        return vec![path_fit];
+        JunctionGraph { junctions, corridors, start: 0, end: 1 }
     }
 
-    for neighbor in neighbors {
-        if path_fit.path.contains(&neighbor) {
-            continue;
-        }
+    // Walks from a junction until the next one, or None for a dead end or a slope going the wrong way
+    fn follow_corridor(trails: &Trails, junctions: &[Point], from: Point, first_step: Point) -> Option<(usize, u64)> {
+        let (mut previous, mut current, mut length) = (from, first_step, 1);
 
-        let mut branch = path_fit.clone();
-        branch.path.insert(neighbor);
+        loop {
+            if let Some(index) = junctions.iter().position(|junction| *junction == current) {
+                return Some((index, length));
+            }
 
-        paths.extend(recursive_path_finding(branch, grid, neighbor));
+            let (next, _) = trails.neighbors(&current).into_iter().find(|(next, _)| *next != previous)?;
+            (previous, current, length) = (current, next, length + 1);
+        }
     }
 
-    paths
-}
+    /// The longest walk from the start to the end that never passes a junction twice.
+    pub fn longest_path(&self) -> Result<u64, String> {
+        if self.junctions.len() > 64 {
+            return Err(format!("{} junctions is too many to track in a u64", self.junctions.len()));
+        }
 
+        self.longest_from(self.start, 1 << self.start).ok_or("There is no way from the start to the end".to_string())
+    }
 
-#[derive(Debug, Clone)]
-struct PathFit {
-    path: HashSet<Point>,
-}
+    // `visited` has a bit set for every junction already on the walk
+    fn longest_from(&self, junction: usize, visited: u64) -> Option<u64> {
+        if junction == self.end {
+            return Some(0);
+        }
 
-impl PathFit {
-    fn contains(&self, pos: &Point) -> bool {
-        self.path.contains(pos)
+        self.corridors[junction].iter()
+            .filter(|(next, _)| visited & (1 << next) == 0)
+            .filter_map(|(next, length)| self.longest_from(*next, visited | (1 << next)).map(|rest| rest + length))
+            .max()
     }
 }
 
-struct Grid {
-    nodes: HashMap<Point, char>,
-    total_size: Point,
-    start: Point,
-    end: Point,
+pub struct TrailMap {
+    pub trails: Grid<char>,
+    pub start: Point,
+    pub end: Point,
 }
 
-impl Grid {
+impl TrailMap {
     fn is_movable(&self, pos: &Point) -> bool {
-        if let Some(node) = self.nodes.get(pos) {
-            return node != &'#'
-        }
-
-        false
+        self.trails.get(*pos).is_some_and(|node| node != &'#')
     }
-    fn parse(input: String) -> Self {
-        let mut y_index = (input.lines().count() as i32);
-        let mut map: HashMap<Point, char> = HashMap::new();
-        let mut total_size: Point = Point::parse(input.lines().nth(0).unwrap().chars().count(), y_index as usize);
-        y_index -= 1;
-
-        for (y, line) in input.lines().enumerate() {
-            let mut x_index = 0;
-            for (x, node) in line.chars().enumerate() {
-                map.insert(Point::new(x_index, y_index), node);
-                x_index += 1;
-            }
-            y_index = y_index - 1;
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let trails = Grid::parse(input, "a path, forest or slope", |node| "#.^>v<".contains(node).then_some(node))?;
+        let size = trails.size();
+
+        let map = TrailMap { trails, start: Point::new(1, size.y - 1), end: Point::new(size.x - 2, 0) };
+        if !map.is_movable(&map.start) || !map.is_movable(&map.end) {
+            return Err(ParseError::new("expected openings next to the top left and bottom right corners"));
         }
 
-        Grid { nodes: map, total_size, start: Point::new(1, total_size.y - 1), end: Point::new(total_size.x - 2, 0) }
+        Ok(map)
     }
 
     fn get_neighbors_part2(&self, pos: Point) -> Vec<Point> {
-        let directions: Vec<Point> = vec![NORTH, SOUTH, EAST, WEST];
+        let directions: Vec<Direction> = vec![Direction::North, Direction::South, Direction::East, Direction::West];
 
         let mut neighbor_points: Vec<Point> = Vec::new();
 
         directions.iter().for_each(|dir| {
-            if let Some(node) = self.nodes.get(&(*dir + pos)) {
+            if let Some(node) = self.trails.get(pos + *dir) {
                 if node != &'#' {
-                    neighbor_points.push(*dir + pos);
+                    neighbor_points.push(pos + *dir);
                 }
             }
         });
@@ -261,21 +175,21 @@ impl Grid {
 
 
     fn get_neighbors(&self, pos: Point) -> Vec<Point> {
-        let current_node = self.nodes.get(&pos).unwrap();
-        let directions: Vec<Point> = match current_node {
-            &'^' => vec![NORTH],
-            &'v' => vec![SOUTH],
-            &'<' => vec![WEST],
-            &'>' => vec![EAST],
-            &_ => vec![SOUTH, NORTH, EAST, WEST]
+        let current_node = &self.trails[pos];
+        let directions: Vec<Direction> = match current_node {
+            &'^' => vec![Direction::North],
+            &'v' => vec![Direction::South],
+            &'<' => vec![Direction::West],
+            &'>' => vec![Direction::East],
+            &_ => vec![Direction::South, Direction::North, Direction::East, Direction::West]
         };
 
         let mut neighbor_points: Vec<Point> = Vec::new();
 
         directions.iter().for_each(|dir| {
-            if let Some(node) = self.nodes.get(&(*dir + pos)) {
+            if let Some(node) = self.trails.get(pos + *dir) {
                 if node != &'#' {
-                    neighbor_points.push(*dir + pos);
+                    neighbor_points.push(pos + *dir);
                 }
             }
         });
@@ -284,23 +198,21 @@ impl Grid {
     }
 
     fn print_grid(&self, movement: &Vec<Point>) {
-        for y in (0..self.total_size.y).rev() {
-            for x in 0..self.total_size.x {
-                if movement.contains(&Point::new(x, y)) {
-                    print!("O");
-                } else {
-                    //print!("{}", self.map.get(&Point::new(x, y)).unwrap().heat_loss);
-                    print!("{}", self.nodes.get(&Point::new(x, y)).unwrap());
-                }
+        let mut marked = self.trails.clone();
+        for pos in movement {
+            if let Some(node) = marked.get_mut(*pos) {
+                *node = 'O';
             }
-            println!("");
         }
+
+        println!("{}", marked);
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day_23::{a_star_path_finding, find_a_star_path, find_longest_path};
+    use crate::days::day_23::{find_longest_path, JunctionGraph, longest_route, parse};
+    use crate::domain::point::Point;
 
     #[test]
     fn can_find_paths() {
@@ -328,9 +240,9 @@ mod tests {
 #.....###...###...#...#
 #####################.#"#;
 
-        let result = find_longest_path(input.to_string(), false);
+        let result = find_longest_path(&parse(input).unwrap(), false);
 
-        assert_eq!(result, 94);
+        assert_eq!(result, Ok(94));
     }
 
     #[test]
@@ -359,9 +271,46 @@ mod tests {
 #.....###...###...#...#
 #####################.#"#;
 
-        let result = find_longest_path(input.to_string(), true);
+        let result = find_longest_path(&parse(input).unwrap(), true);
 
-        assert_eq!(result, 154);
+        assert_eq!(result, Ok(154));
+    }
+
+    #[test]
+    fn corridors_join_the_junctions() {
+        let input = r#"#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#"#;
+
+        let map = parse(input).unwrap();
+        let graph = JunctionGraph::build(&map, true);
+
+        assert_eq!(graph.junctions.len(), 9);
+        let (first_junction, length) = graph.corridors[graph.start][0];
+        assert_eq!(graph.corridors[graph.start].len(), 1);
+        assert_eq!((graph.junctions[first_junction], length), (Point::from_screen(3, 5, 23), 15));
+        assert_eq!(JunctionGraph::build(&map, false).longest_path(), Ok(94));
+        assert_eq!(graph.longest_path(), Ok(154));
     }
 
     #[test]
@@ -390,8 +339,20 @@ mod tests {
 #.....###...###...#...#
 #####################.#"#;
 
-        let result = find_a_star_path(input.to_string(), false);
+        let map = parse(input).unwrap();
+        let route = longest_route(&map, false).unwrap();
 
-        assert_eq!(result, 94);
+        assert_eq!(route.cost, 94);
+        assert_eq!(route.path.len(), 95);
+        assert_eq!(route.path.first(), Some(&map.start));
+        assert_eq!(route.path.last(), Some(&map.end));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn a_blocked_map_is_unsolved() {
+        let map = parse("#.###\n#.#.#\n###.#").unwrap();
+
+        assert_eq!(find_longest_path(&map, true), Err("There is no way from the start to the end".to_string()));
+        assert!(longest_route(&map, true).is_err());
+    }
+}
@@ -1,21 +1,29 @@
-use std::fmt::Display;
-use itertools::min;
-use crate::domain::vector3::Vector3;
-use crate::domain::vector3_large::Vector3Large;
-
-pub fn part_one(input: String) -> impl Display {
-    let mut hail:Vec<Hail> = input.lines().map(|line| Hail::parse(line)).collect();
-    get_intersections(hail)
+use std::ops::RangeInclusive;
+use num::{BigInt, BigRational, Signed, ToPrimitive, Zero};
+use crate::domain::geometry::{Fraction, Intersection, Line, Line2, Line3};
+use crate::domain::point::Point;
+use crate::domain::vector3::{Vector3, Vector3Large};
+use crate::answer::Answer;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
+
+pub fn parse(input: &str) -> Result<Vec<Hail>, ParseError> {
+    parse_lines(input, Hail::parse)
+}
+
+/// Part one only counts paths crossing inside this range, on both x and y.
+pub const TEST_AREA: RangeInclusive<i128> = 200000000000000..=400000000000000;
+
+pub fn part_one(input: &Vec<Hail>) -> Answer {
+    get_intersections(input, &TEST_AREA).into()
 }
 
-fn get_intersections(hail_stones:Vec<Hail>) -> i32 {
+pub fn get_intersections(hail_stones:&Vec<Hail>, test_area: &RangeInclusive<i128>) -> i32 {
     let mut counter = 0;
-    let min_zone = Vector3Large::new(200000000000000.0, 200000000000000.0, 0.0);
-    let max_zone = Vector3Large::new(400000000000000.0, 400000000000000.0, 0.0);
     for i in 0..hail_stones.len() {
         for j in i + 1..hail_stones.len() {
             let (hail_1, hail_2) = (&hail_stones[i], &hail_stones[j]);
-            if check_intersection_2(hail_1.clone(), hail_2.clone(), min_zone, max_zone) {
+            if paths_cross_inside(hail_1, hail_2, test_area) {
                 counter += 1;
             }
         }
@@ -24,105 +32,266 @@ fn get_intersections(hail_stones:Vec<Hail>) -> i32 {
     counter
 }
 
-pub fn part_two(input: String) -> impl Display {
-    0
+pub fn part_two(input: &Vec<Hail>) -> Answer {
+    throw_rock(input).into()
 }
 
-fn check_intersection(hail_1: Hail, hail_2: Hail, min_zone: Vector3Large, max_zone: Vector3Large) -> bool {
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<Hail>;
+
+    fn day(&self) -> u32 {
+        24
+    }
+
+    fn title(&self) -> &'static str {
+        "Never Tell Me The Odds"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Hail>, ParseError> {
+        parse(input)
+    }
 
-    // Lets get the hail at min position x,y
-    let relative_velocity = hail_2.velocity - hail_1.velocity;
-    let relative_position = hail_2.position - hail_1.position;
+    fn part_one(&self, input: &Vec<Hail>) -> Answer {
+        part_one(input)
+    }
 
-    let xy_dot = relative_velocity.x * relative_position.x + relative_velocity.y * relative_position.y;
+    fn part_two(&self, input: &Vec<Hail>) -> Answer {
+        part_two(input)
+    }
+}
 
-    if xy_dot < 0.0 {
-        let collision_time = -(relative_position.x * relative_velocity.x + relative_position.y * relative_position.y) / xy_dot;
-        let collision_point = Vector3Large::new(hail_1.position.x + hail_1.velocity.x * collision_time, hail_1.position.y + hail_1.velocity.y * collision_time, 0.0);
+/// The sum of the coordinates the rock is thrown from, so it hits every hailstone. Errors when no
+/// single throw with whole numbers does.
+pub fn throw_rock(hail_stones: &Vec<Hail>) -> Result<i64, String> {
+    // Any three hailstones pin down the rock, but some triples give a singular system so keep trying.
+    // A throw hitting them all would solve the first system that isn't, so it is the only candidate.
+    for i in 1..hail_stones.len() {
+        for j in i + 1..hail_stones.len() {
+            if let Some(solution) = solve_rock(&hail_stones[0], &hail_stones[i], &hail_stones[j]) {
+                let rock = rock_from(&solution)?;
+                if let Some(missed) = hail_stones.iter().position(|hail| !rock.hits(hail)) {
+                    return Err(format!("The only possible throw, {} @ {}, misses hailstone {}", rock.position, rock.velocity, missed + 1));
+                }
 
-        println!("{}", collision_point);
-        if collision_point.x > min_zone.x && collision_point.x < max_zone.x && collision_point.y > min_zone.y && collision_point.y < max_zone.y
-        {
-            return true
+                let position = rock.position.cast::<i128>();
+                return i64::try_from(position.x + position.y + position.z).map_err(|_| "The rock's coordinates add up to more than an i64".to_string());
+            }
         }
     }
 
-    false
+    Err("No three hailstones pin down a single rock throw".to_string())
 }
 
-fn check_intersection_2(hail_1: Hail, hail_2: Hail, min_zone: Vector3Large, max_zone: Vector3Large) -> bool {
-    if hail_1.a * hail_2.b == hail_1.b * hail_2.a {
-        return false;
+// The rock (P, V) hits a hailstone (p, v) when (P - p) x (V - v) = 0. The P x V term is shared by every
+// hailstone, so subtracting the equations of two hailstones leaves a linear system in P and V:
+// P x (v_j - v_i) + (p_j - p_i) x V = p_j x v_j - p_i x v_i
+fn solve_rock(hail_1: &Hail, hail_2: &Hail, hail_3: &Hail) -> Option<Vec<BigRational>> {
+    let mut rows:Vec<Vec<BigRational>> = Vec::new();
+    let (p_i, v_i) = hail_1.wide();
+
+    for other in [hail_2, hail_3] {
+        let (p_j, v_j) = other.wide();
+        let (dp, dv) = (p_j - p_i, v_j - v_i);
+        let rhs = p_j.cross(v_j) - p_i.cross(v_i);
+
+        let coefficients = [
+            [0, dv.z, -dv.y, 0, -dp.z, dp.y, rhs.x],
+            [-dv.z, 0, dv.x, dp.z, 0, -dp.x, rhs.y],
+            [dv.y, -dv.x, 0, -dp.y, dp.x, 0, rhs.z],
+        ];
+        for row in coefficients {
+            rows.push(row.into_iter().map(|value| BigRational::from_integer(BigInt::from(value))).collect());
+        }
     }
 
-    let x = (hail_1.c * hail_2.b - hail_2.c * hail_1.b) / (hail_1.a * hail_2.b - hail_2.a * hail_1.b);
-    let y = (hail_2.c * hail_1.a - hail_1.c * hail_2.a) / (hail_1.a * hail_2.b - hail_2.a * hail_1.b);
+    gaussian_elimination(rows)
+}
+
+// The solved system as a hailstone, as long as it is made of whole numbers that fit
+fn rock_from(solution: &[BigRational]) -> Result<Hail, String> {
+    let values = solution.iter()
+        .map(|value| match value.is_integer() {
+            true => value.to_integer().to_i64().ok_or(format!("The rock's coordinate {} doesn't fit an i64", value)),
+            false => Err(format!("The rock would need to be thrown from or at a fraction, {}", value))
+        })
+        .collect::<Result<Vec<i64>, String>>()?;
+
+    Ok(Hail { position: Vector3::new(values[0], values[1], values[2]), velocity: Vector3::new(values[3], values[4], values[5]) })
+}
+
+// Solves an augmented matrix, returning None when the system has no single solution
+fn gaussian_elimination(mut rows: Vec<Vec<BigRational>>) -> Option<Vec<BigRational>> {
+    let size = rows.len();
 
-    if x >= min_zone.x && x <= max_zone.x && y >= min_zone.y && y <= max_zone.y {
-        return [hail_1, hail_2].iter().all(|h| (x - h.position.x) * h.velocity.x >= 0.0 && (y - h.position.y) * h.velocity.y >= 0.0);
+    for column in 0..size {
+        let pivot = (column..size).find(|&row| !rows[row][column].is_zero())?;
+        rows.swap(column, pivot);
+
+        let pivot_value = rows[column][column].clone();
+        rows[column].iter_mut().for_each(|value| *value = &*value / &pivot_value);
+
+        for row in 0..size {
+            if row == column || rows[row][column].is_zero() {
+                continue;
+            }
+            let factor = rows[row][column].clone();
+            for k in column..=size {
+                let update = &factor * &rows[column][k];
+                rows[row][k] = &rows[row][k] - update;
+            }
+        }
     }
 
-    false
+    Some(rows.iter().map(|row| row[size].clone()).collect())
 }
 
-#[derive(Debug, PartialEq, Clone, Copy, PartialOrd)]
-struct Hail {
-    position: Vector3Large,
-    velocity: Vector3Large,
-    a: f64,
-    b: f64,
-    c: f64
+// Looking down on the hail, ignoring z, do the paths cross ahead of both hailstones and inside the area
+fn paths_cross_inside(hail_1: &Hail, hail_2: &Hail, test_area: &RangeInclusive<i128>) -> bool {
+    let crossing = hail_1.flat_path().intersection(&hail_2.flat_path());
+    let inside = |value: Fraction| value >= Fraction::from(*test_area.start()) && value <= Fraction::from(*test_area.end());
+
+    match crossing {
+        Intersection::Crossing { at, .. } => crossing.in_future() && inside(at.x) && inside(at.y),
+        _ => false
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd)]
+pub struct Hail {
+    pub position: Vector3Large,
+    pub velocity: Vector3Large
 }
 impl Hail {
-    fn parse(input_line: &str) -> Self {
-        let [start, velocity] = input_line.split("@").map(|s| Vector3Large::parse(s)).next_chunk().unwrap();
+    pub fn parse(input_line: &str) -> Result<Self, ParseError> {
+        let (position, velocity) = input_line.split_once("@").ok_or_else(|| ParseError::missing(input_line, "@ followed by the velocity"))?;
+        let position = Vector3::parse(input_line, position)?;
+        let velocity = Vector3::parse(input_line, velocity)?;
+
+        Ok(Hail { position, velocity })
+    }
+
+    fn flat_path(&self) -> Line2 {
+        Line::new(Point::new(self.position.x, self.position.y).cast(), Point::new(self.velocity.x, self.velocity.y).cast())
+    }
+
+    fn path(&self) -> Line3 {
+        Line::new(self.position.cast(), self.velocity.cast())
+    }
+
+    // Products of puzzle sized positions and velocities overflow an i64
+    fn wide(&self) -> (Vector3<i128>, Vector3<i128>) {
+        (self.position.cast(), self.velocity.cast())
+    }
 
-        Hail { position: start, velocity, a: velocity.y, b: -velocity.x, c: velocity.y * start.x - velocity.x * start.y}
+    // Whether the two are ever in the same place at the same time, from now on
+    fn hits(&self, other: &Hail) -> bool {
+        match self.path().intersection(&other.path()) {
+            Intersection::Crossing { t, u, .. } => t == u && !t.is_negative(),
+            // On the same line, so they meet unless they are drawing apart or keeping their distance
+            Intersection::Colinear => {
+                let ((position, velocity), (other_position, other_velocity)) = (self.wide(), other.wide());
+                let (offset, closing) = (other_position - position, velocity - other_velocity);
+                match closing == Vector3::new(0, 0, 0) {
+                    true => offset == Vector3::new(0, 0, 0),
+                    false => offset.dot(closing) >= 0
+                }
+            }
+            _ => false
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day_24::{check_intersection, check_intersection_2, Hail};
-    use crate::domain::vector3_large::Vector3Large;
+    use crate::days::day_24::{get_intersections, Hail, parse, paths_cross_inside, throw_rock};
 
     #[test]
     fn can_get_hail_intersection_in_zone() {
-        let hail_1 = Hail::parse(r#"19, 13, 30 @ -2,  1, -2"#);
-        let hail_2 = Hail::parse(r#"18, 19, 22 @ -1, -1, -2"#);
+        let hail_1 = Hail::parse(r#"19, 13, 30 @ -2,  1, -2"#).unwrap();
+        let hail_2 = Hail::parse(r#"18, 19, 22 @ -1, -1, -2"#).unwrap();
 
-        let result = check_intersection_2(hail_1, hail_2, Vector3Large::new(7.0,7.0,7.0), Vector3Large::new(27.0,27.0,27.0));
+        let result = paths_cross_inside(&hail_1, &hail_2, &(7..=27));
 
         assert_eq!(result, true);
     }
 
     #[test]
     fn can_get_hail_intersection_in_past() {
-        let hail_1 = Hail::parse(r#"19, 13, 30 @ -2, 1, -2"#);
-        let hail_2 = Hail::parse(r#"20, 19, 15 @ 1, -5, -3"#);
+        let hail_1 = Hail::parse(r#"19, 13, 30 @ -2, 1, -2"#).unwrap();
+        let hail_2 = Hail::parse(r#"20, 19, 15 @ 1, -5, -3"#).unwrap();
 
-        let result = check_intersection_2(hail_1, hail_2, Vector3Large::new(7.0,7.0,7.0), Vector3Large::new(27.0,27.0,27.0));
+        let result = paths_cross_inside(&hail_1, &hail_2, &(7..=27));
 
         assert_eq!(result, false);
     }
 
     #[test]
     fn can_get_intersection_out_of_area() {
-        let hail_1 = Hail::parse(r#"19, 13, 30 @ -2, 1, -2"#);
-        let hail_2 = Hail::parse(r#"12, 31, 28 @ -1, -2, -1"#);
+        let hail_1 = Hail::parse(r#"19, 13, 30 @ -2, 1, -2"#).unwrap();
+        let hail_2 = Hail::parse(r#"12, 31, 28 @ -1, -2, -1"#).unwrap();
 
-        let result = check_intersection_2(hail_1, hail_2, Vector3Large::new(7.0,7.0,7.0), Vector3Large::new(27.0,27.0,27.0));
+        let result = paths_cross_inside(&hail_1, &hail_2, &(7..=27));
 
         assert_eq!(result, false);
     }
 
     #[test]
     fn parallel_returns_false() {
-        let hail_1 = Hail::parse(r#"18, 19, 22 @ -1, -1, -2"#);
-        let hail_2 = Hail::parse(r#"20, 25, 34 @ -2, -2, -4"#);
+        let hail_1 = Hail::parse(r#"18, 19, 22 @ -1, -1, -2"#).unwrap();
+        let hail_2 = Hail::parse(r#"20, 25, 34 @ -2, -2, -4"#).unwrap();
 
-        let result = check_intersection_2(hail_1, hail_2, Vector3Large::new(7.0,7.0,7.0), Vector3Large::new(27.0,27.0,27.0));
+        let result = paths_cross_inside(&hail_1, &hail_2, &(7..=27));
 
         assert_eq!(result, false);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn can_count_crossings_in_area() {
+        let input = r#"19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3"#;
+
+        let hail = parse(input).unwrap();
+
+        assert_eq!(get_intersections(&hail, &(7..=27)), 2);
+    }
+
+    #[test]
+    fn can_find_rock_throw() {
+        let input = r#"19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3"#;
+
+        let hail = parse(input).unwrap();
+
+        assert_eq!(throw_rock(&hail), Ok(47));
+    }
+
+    #[test]
+    fn throws_that_miss_a_hailstone_are_rejected() {
+        let input = r#"19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 16 @  1, -5, -3"#;
+
+        let hail = parse(input).unwrap();
+
+        assert!(throw_rock(&hail).is_err());
+    }
+
+    #[test]
+    fn hail_needs_three_coordinates() {
+        let error = parse("19, 13, 30 @ -2,  1, -2\n18, 19 @ -1, -1, -2").err().unwrap();
+
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.column, Some(1));
+        assert_eq!(error.message, "expected x,y,z, found \"18, 19\"");
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use priority_queue::PriorityQueue;
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+pub fn parse(input: &str) -> Result<WiringGraph, ParseError> {
+    WiringGraph::parse(input)
+}
+
+pub fn part_one(input: &WiringGraph) -> Answer {
+    split_components(input).into()
+}
+
+pub fn part_two(input: &WiringGraph) -> Answer {
+    "Merry Christmas!".into()
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = WiringGraph;
+
+    fn day(&self) -> u32 {
+        25
+    }
+
+    fn title(&self) -> &'static str {
+        "Snowverload"
+    }
+
+    fn parse(&self, input: &str) -> Result<WiringGraph, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self, input: &WiringGraph) -> Answer {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &WiringGraph) -> Answer {
+        part_two(input)
+    }
+}
+
+/// The sizes of the two groups multiplied together, or an error when they aren't joined by
+/// exactly three wires as the puzzle promises.
+pub fn split_components(graph: &WiringGraph) -> Result<usize, String> {
+    let total = graph.edges.len();
+    let (cut, group_size) = graph.minimum_cut();
+
+    if cut != 3 {
+        return Err(format!("Expected to cut 3 wires but the minimum cut was {}", cut));
+    }
+
+    Ok(group_size * (total - group_size))
+}
+
+pub struct WiringGraph {
+    pub names: Vec<String>,
+    pub edges: Vec<HashMap<usize, u32>>
+}
+
+impl WiringGraph {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut graph = WiringGraph { names: vec![], edges: vec![] };
+        let mut ids:HashMap<String, usize> = HashMap::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let (name, connections) = line.split_once(":").ok_or_else(|| ParseError::missing(line, ": followed by the connected components").on_line(index + 1))?;
+            if name.trim().is_empty() {
+                return Err(ParseError::at(line, name, "expected a component name").on_line(index + 1));
+            }
+            let component = graph.get_or_insert(&mut ids, name.trim());
+            for connected in connections.split_whitespace() {
+                let other = graph.get_or_insert(&mut ids, connected);
+                graph.edges[component].insert(other, 1);
+                graph.edges[other].insert(component, 1);
+            }
+        }
+
+        Ok(graph)
+    }
+
+    fn get_or_insert(&mut self, ids: &mut HashMap<String, usize>, name: &str) -> usize {
+        if let Some(id) = ids.get(name) {
+            return *id;
+        }
+
+        let id = self.names.len();
+        ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        self.edges.push(HashMap::new());
+        id
+    }
+
+    // Stoer-Wagner: each phase grows a set from an arbitrary node by always adding the most tightly
+    // connected node. The last node added gives a cut of the phase, then merges into the one before it.
+    // Returns the weight of the smallest cut found and the number of components on one side of it.
+    fn minimum_cut(&self) -> (u32, usize) {
+        let mut edges = self.edges.clone();
+        let mut group_sizes:Vec<usize> = vec![1; edges.len()];
+        let mut active:Vec<usize> = (0..edges.len()).collect();
+        let mut best = (u32::MAX, 0);
+
+        while active.len() > 1 {
+            let mut frontier:PriorityQueue<usize, u32> = PriorityQueue::new();
+            active.iter().for_each(|node| { frontier.push(*node, 0); });
+
+            let mut previous = active[0];
+            let mut last = active[0];
+            let mut cut_of_phase = 0;
+
+            while let Some((node, weight)) = frontier.pop() {
+                previous = last;
+                last = node;
+                cut_of_phase = weight;
+
+                for (neighbor, edge_weight) in &edges[node] {
+                    frontier.change_priority_by(neighbor, |priority| *priority += edge_weight);
+                }
+            }
+
+            if cut_of_phase < best.0 {
+                best = (cut_of_phase, group_sizes[last]);
+            }
+
+            let merged = std::mem::take(&mut edges[last]);
+            for (neighbor, weight) in merged {
+                edges[neighbor].remove(&last);
+                if neighbor == previous {
+                    continue;
+                }
+                *edges[previous].entry(neighbor).or_insert(0) += weight;
+                *edges[neighbor].entry(previous).or_insert(0) += weight;
+            }
+            group_sizes[previous] += group_sizes[last];
+            active.retain(|node| *node != last);
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day_25::{split_components, WiringGraph};
+
+    #[test]
+    fn can_parse_wiring() {
+        let input = r#"jqt: rhn xhk nvd
+rsh: frs pzl lsr"#;
+
+        let graph = WiringGraph::parse(input).unwrap();
+
+        assert_eq!(graph.names.len(), 8);
+        assert_eq!(graph.edges[0].len(), 3);
+    }
+
+    #[test]
+    fn can_split_components() {
+        let input = r#"jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr"#;
+
+        let graph = WiringGraph::parse(input).unwrap();
+
+        assert_eq!(graph.minimum_cut().0, 3);
+        assert_eq!(split_components(&graph), Ok(54));
+    }
+
+    #[test]
+    fn other_cut_sizes_are_reported() {
+        let graph = WiringGraph::parse("a: b\nb: c").unwrap();
+
+        assert_eq!(split_components(&graph), Err("Expected to cut 3 wires but the minimum cut was 1".to_string()));
+    }
+}
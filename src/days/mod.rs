@@ -1,24 +1,63 @@
-pub mod day_01;
-pub mod day_02;
-pub mod day_03;
-pub mod day_04;
-pub mod day_05;
-pub mod day_06;
-pub mod day_07;
-pub mod day_08;
-pub mod day_09;
-pub mod day_10;
-pub mod day_11;
-pub mod day_12;
-pub mod day_13;
-pub mod day_14;
-pub mod day_15;
-pub mod day_16;
-pub mod day_17;
-pub mod day_18;
-pub mod day_19;
-pub mod day_20;
-pub mod day_21;
-pub mod day_22;
-pub mod day_23;
-pub mod day_24;
\ No newline at end of file
+use std::sync::Arc;
+use crate::solution::Puzzle;
+
+// Declares each day module and registers its solution, so adding a day is a single line here
+macro_rules! solutions {
+    ($($module:ident::$solution:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        pub fn all_solutions() -> Vec<Arc<dyn Puzzle>> {
+            vec![$(Arc::new($module::$solution)),*]
+        }
+    };
+}
+
+solutions!(
+    day_01::Day01,
+    day_02::Day02,
+    day_03::Day03,
+    day_04::Day04,
+    day_05::Day05,
+    day_06::Day06,
+    day_07::Day07,
+    day_08::Day08,
+    day_09::Day09,
+    day_10::Day10,
+    day_11::Day11,
+    day_12::Day12,
+    day_13::Day13,
+    day_14::Day14,
+    day_15::Day15,
+    day_16::Day16,
+    day_17::Day17,
+    day_18::Day18,
+    day_19::Day19,
+    day_20::Day20,
+    day_21::Day21,
+    day_22::Day22,
+    day_23::Day23,
+    day_24::Day24,
+    day_25::Day25
+);
+
+pub fn get_solution(day: u32) -> Option<Arc<dyn Puzzle>> {
+    all_solutions().into_iter().find(|solution| solution.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::{all_solutions, get_solution};
+
+    #[test]
+    fn registry_has_every_day_in_order() {
+        let days:Vec<u32> = all_solutions().iter().map(|solution| solution.day()).collect();
+
+        assert_eq!(days, (1..=25).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn can_look_up_a_single_day() {
+        assert_eq!(get_solution(17).unwrap().title(), "Clumsy Crucible");
+        assert_eq!(get_solution(26).is_none(), true);
+    }
+}
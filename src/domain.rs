@@ -1,3 +1,6 @@
+pub mod direction;
+pub mod geometry;
+pub mod grid;
 pub mod point;
-pub mod vector3;
-pub mod vector3_large;
\ No newline at end of file
+pub mod search;
+pub mod vector3;
\ No newline at end of file
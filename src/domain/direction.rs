@@ -0,0 +1,117 @@
+use std::fmt;
+use std::ops::Add;
+use crate::domain::point::{EAST, NORTH, Point, SOUTH, WEST};
+
+/// One of the four ways to step across a grid. North is up the page, which is towards higher y
+/// in the `Point` convention.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West
+}
+
+impl Direction {
+    /// Clockwise, starting from north.
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    pub fn turn_left(&self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South
+        }
+    }
+
+    pub fn turn_right(&self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East
+        }
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        matches!(self, Direction::East | Direction::West)
+    }
+
+    /// The step one tile this way, one of the compass constants in `point`.
+    pub fn offset(&self) -> Point {
+        match self {
+            Direction::North => NORTH,
+            Direction::East => EAST,
+            Direction::South => SOUTH,
+            Direction::West => WEST
+        }
+    }
+
+    /// The direction a single step of `offset` goes, if it is one.
+    pub fn from_offset(offset: Point) -> Option<Self> {
+        Direction::ALL.into_iter().find(|direction| direction.offset() == offset)
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, direction: Direction) -> Point {
+        self + direction.offset()
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::North => write!(f, "north"),
+            Direction::East => write!(f, "east"),
+            Direction::South => write!(f, "south"),
+            Direction::West => write!(f, "west")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::direction::Direction;
+    use crate::domain::point::{NORTH, Point};
+
+    #[test]
+    fn turning_four_times_comes_back_round() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_left().turn_left().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+        }
+    }
+
+    #[test]
+    fn north_is_up() {
+        assert_eq!(Direction::North.offset(), NORTH);
+        assert_eq!(Point::new(2, 2) + Direction::North, Point::new(2, 3));
+        assert_eq!(Direction::East.turn_left(), Direction::North);
+    }
+
+    #[test]
+    fn can_find_direction_of_a_step() {
+        assert_eq!(Direction::from_offset(Point::new(-1, 0)), Some(Direction::West));
+        assert_eq!(Direction::from_offset(Point::new(1, 1)), None);
+    }
+}
@@ -0,0 +1,178 @@
+use num::rational::Ratio;
+use num::Signed;
+use crate::domain::point::Point;
+use crate::domain::vector3::Vector3;
+
+/// An exact fraction. Puzzle coordinates run into the hundreds of trillions, past where an `f64`
+/// can be trusted once they are multiplied together.
+pub type Fraction = Ratio<i128>;
+
+pub type Line2 = Line<Point<i128>>;
+pub type Line3 = Line<Vector3<i128>>;
+
+/// The points `origin + direction * t` for every `t`. Treated as a ray, only `t >= 0` counts.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Line<V> {
+    pub origin: V,
+    pub direction: V
+}
+
+/// How two lines meet. `t` and `u` say how far along the first and second line the crossing is.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Intersection<V> {
+    Crossing { at: V, t: Fraction, u: Fraction },
+    /// Side by side, never meeting
+    Parallel,
+    /// The same line, meeting everywhere
+    Colinear,
+    /// Neither parallel nor meeting, which only happens in three dimensions
+    Skew
+}
+
+impl<V> Intersection<V> {
+    /// Whether the rays from both origins meet, rather than only the lines behind them.
+    pub fn in_future(&self) -> bool {
+        matches!(self, Intersection::Crossing { t, u, .. } if !t.is_negative() && !u.is_negative())
+    }
+
+    /// Whether the lines cross, but before one of them started.
+    pub fn in_past(&self) -> bool {
+        matches!(self, Intersection::Crossing { t, u, .. } if t.is_negative() || u.is_negative())
+    }
+}
+
+impl<V> Line<V> {
+    pub fn new(origin: V, direction: V) -> Self {
+        Line { origin, direction }
+    }
+}
+
+impl Line<Point<i128>> {
+    pub fn at(&self, t: Fraction) -> Point<Fraction> {
+        Point::new(
+            Fraction::from(self.origin.x) + Fraction::from(self.direction.x) * t,
+            Fraction::from(self.origin.y) + Fraction::from(self.direction.y) * t
+        )
+    }
+
+    pub fn intersection(&self, other: &Line2) -> Intersection<Point<Fraction>> {
+        let offset = other.origin - self.origin;
+        let denominator = self.direction.cross(other.direction);
+
+        if denominator == 0 {
+            return if offset.cross(self.direction) == 0 { Intersection::Colinear } else { Intersection::Parallel };
+        }
+
+        // Crossing both sides of origin + direction * t = other.origin + other.direction * u with
+        // one direction cancels it out, leaving the other's distance
+        let t = Fraction::new(offset.cross(other.direction), denominator);
+        let u = Fraction::new(offset.cross(self.direction), denominator);
+
+        Intersection::Crossing { at: self.at(t), t, u }
+    }
+}
+
+impl Line<Vector3<i128>> {
+    pub fn at(&self, t: Fraction) -> Vector3<Fraction> {
+        Vector3::new(
+            Fraction::from(self.origin.x) + Fraction::from(self.direction.x) * t,
+            Fraction::from(self.origin.y) + Fraction::from(self.direction.y) * t,
+            Fraction::from(self.origin.z) + Fraction::from(self.direction.z) * t
+        )
+    }
+
+    pub fn intersection(&self, other: &Line3) -> Intersection<Vector3<Fraction>> {
+        let offset = other.origin - self.origin;
+        let normal = self.direction.cross(other.direction);
+
+        if normal == Vector3::new(0, 0, 0) {
+            return if offset.cross(self.direction) == Vector3::new(0, 0, 0) { Intersection::Colinear } else { Intersection::Parallel };
+        }
+        if offset.dot(normal) != 0 {
+            return Intersection::Skew;
+        }
+
+        // As in two dimensions, but the crossed vectors are projected onto the normal to get a number
+        let squared_length = normal.dot(normal);
+        let t = Fraction::new(offset.cross(other.direction).dot(normal), squared_length);
+        let u = Fraction::new(offset.cross(self.direction).dot(normal), squared_length);
+
+        Intersection::Crossing { at: self.at(t), t, u }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::geometry::{Fraction, Intersection, Line2, Line3};
+    use crate::domain::point::Point;
+    use crate::domain::vector3::Vector3;
+
+    #[test]
+    fn can_find_crossing_in_two_dimensions() {
+        let first = Line2::new(Point::new(19, 13), Point::new(-2, 1));
+        let second = Line2::new(Point::new(18, 19), Point::new(-1, -1));
+
+        let intersection = first.intersection(&second);
+
+        match intersection {
+            Intersection::Crossing { at, .. } => assert_eq!(at, Point::new(Fraction::new(43, 3), Fraction::new(46, 3))),
+            _ => panic!("expected the lines to cross")
+        }
+        assert!(intersection.in_future());
+    }
+
+    #[test]
+    fn crossings_behind_a_ray_are_in_the_past() {
+        let first = Line2::new(Point::new(19, 13), Point::new(-2, 1));
+        let second = Line2::new(Point::new(20, 19), Point::new(1, -5));
+
+        let intersection = first.intersection(&second);
+
+        assert!(intersection.in_past());
+        assert!(!intersection.in_future());
+    }
+
+    #[test]
+    fn can_tell_parallel_from_colinear() {
+        let line = Line2::new(Point::new(18, 19), Point::new(-1, -1));
+
+        assert_eq!(line.intersection(&Line2::new(Point::new(20, 25), Point::new(-2, -2))), Intersection::Parallel);
+        assert_eq!(line.intersection(&Line2::new(Point::new(20, 21), Point::new(3, 3))), Intersection::Colinear);
+    }
+
+    #[test]
+    fn handles_puzzle_sized_coordinates() {
+        let first = Line2::new(Point::new(400_000_000_000_000, 0), Point::new(-1, 1));
+        let second = Line2::new(Point::new(0, 0), Point::new(1, 1));
+
+        match first.intersection(&second) {
+            Intersection::Crossing { at, t, .. } => {
+                assert_eq!(at, Point::new(Fraction::from(200_000_000_000_000), Fraction::from(200_000_000_000_000)));
+                assert_eq!(t, Fraction::from(200_000_000_000_000));
+            },
+            _ => panic!("expected the lines to cross")
+        }
+    }
+
+    #[test]
+    fn can_find_crossing_in_three_dimensions() {
+        let first = Line3::new(Vector3::new(24, 13, 10), Vector3::new(-3, 1, 2));
+        let second = Line3::new(Vector3::new(19, 13, 30), Vector3::new(-2, 1, -2));
+
+        match first.intersection(&second) {
+            Intersection::Crossing { at, t, u } => {
+                assert_eq!(at, Vector3::new(Fraction::from(9), Fraction::from(18), Fraction::from(20)));
+                assert_eq!((t, u), (Fraction::from(5), Fraction::from(5)));
+            },
+            _ => panic!("expected the lines to cross")
+        }
+    }
+
+    #[test]
+    fn lines_in_different_planes_are_skew() {
+        let first = Line3::new(Vector3::new(0, 0, 0), Vector3::new(1, 0, 0));
+        let second = Line3::new(Vector3::new(0, 0, 1), Vector3::new(0, 1, 0));
+
+        assert_eq!(first.intersection(&second), Intersection::Skew);
+    }
+}
@@ -0,0 +1,295 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use crate::domain::point::{EAST, NORTH, NORTHEAST, NORTHWEST, Point, SOUTH, SOUTHEAST, SOUTHWEST, WEST};
+use crate::error::ParseError;
+
+/// A rectangle of cells stored row by row. Cells are addressed by `Point`s the same way the
+/// compass constants in `point` move: x counts columns from the left, y counts rows up from the
+/// bottom, so the first line of a puzzle input is the row at `height - 1`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    // Top row first, as the rows appear in the input
+    cells: Vec<T>
+}
+
+impl<T> Grid<T> {
+    /// Rows are given top row first and must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, String> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some(index) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!("row {} has {} cells where the first has {}", index + 1, rows[index].len(), width));
+        }
+
+        let height = rows.len();
+        Ok(Grid { width, height, cells: rows.into_iter().flatten().collect() })
+    }
+
+    /// Reads one cell per character, `cell` turning a character into a cell or rejecting it.
+    /// `expected` names the allowed characters in the error for one that isn't.
+    pub fn parse(input: &str, expected: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let width = input.lines().nth(0).unwrap_or_default().chars().count();
+        let mut cells = Vec::new();
+
+        for (index, line) in input.lines().enumerate() {
+            if line.chars().count() != width {
+                return Err(ParseError::at(line, line, format!("expected a row of {} tiles, found {}", width, line.chars().count())).on_line(index + 1));
+            }
+            for (offset, character) in line.char_indices() {
+                match cell(character) {
+                    Some(value) => cells.push(value),
+                    None => return Err(ParseError::at(line, &line[offset..], format!("expected {}, found {:?}", expected, character)).on_line(index + 1))
+                }
+            }
+        }
+
+        let height = if width == 0 { 0 } else { cells.len() / width };
+        Ok(Grid { width, height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Width and height as a point, one past the top right corner.
+    pub fn size(&self) -> Point {
+        Point::parse(self.width, self.height)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0 && point.y >= 0 && (point.x as usize) < self.width && (point.y as usize) < self.height
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        if !self.contains(point) {
+            return None;
+        }
+
+        let (column, row) = point.to_screen(self.height)?;
+        Some(row * self.width + column)
+    }
+
+    fn point_at(&self, offset: usize) -> Point {
+        Point::from_screen(offset % self.width, offset / self.width, self.height)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Every point in the grid, top row first and left to right, like reading the input.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.cells.len()).map(|offset| self.point_at(offset))
+    }
+
+    /// Every cell with its point, in the same order as `points`.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.cells.iter().enumerate().map(|(offset, cell)| (self.point_at(offset), cell))
+    }
+
+    /// The first point, in reading order, whose cell matches.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.cells.iter().position(|cell| predicate(cell)).map(|offset| self.point_at(offset))
+    }
+
+    /// The points north, south, east and west of `point` that are inside the grid.
+    pub fn neighbors(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        [NORTH, SOUTH, EAST, WEST].into_iter().map(move |direction| point + direction).filter(|neighbor| self.contains(*neighbor))
+    }
+
+    /// The four diagonal points around `point` that are inside the grid.
+    pub fn diagonal_neighbors(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        [NORTHEAST, NORTHWEST, SOUTHEAST, SOUTHWEST].into_iter().map(move |direction| point + direction).filter(|neighbor| self.contains(*neighbor))
+    }
+
+    /// All eight points around `point` that are inside the grid.
+    pub fn all_neighbors(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(point).chain(self.diagonal_neighbors(point))
+    }
+
+    /// The rows, top row first.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The row at height `y`, left to right.
+    pub fn row(&self, y: i32) -> Option<&[T]> {
+        let start = self.offset(Point::new(0, y))?;
+        Some(&self.cells[start..start + self.width])
+    }
+
+    /// The column at `x`, top to bottom.
+    pub fn column(&self, x: i32) -> Option<impl Iterator<Item = &T> + '_> {
+        if x < 0 || x as usize >= self.width {
+            return None;
+        }
+
+        Some(self.cells.iter().skip(x as usize).step_by(self.width))
+    }
+
+    /// The columns, left to right, each read top to bottom.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn map<U>(&self, transform: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(transform).collect() }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    /// Mirrors the grid along the line from its top left to its bottom right corner, so rows
+    /// become columns.
+    pub fn transpose(&self) -> Self {
+        let cells = self.columns().flat_map(|column| column.cloned()).collect();
+        Grid { width: self.height, height: self.width, cells }
+    }
+
+    /// Turns the grid a quarter turn clockwise, the left column becoming the top row.
+    pub fn rotate_clockwise(&self) -> Self {
+        let cells = self.columns().flat_map(|column| column.cloned().collect::<Vec<T>>().into_iter().rev()).collect();
+        Grid { width: self.height, height: self.width, cells }
+    }
+
+    /// Turns the grid a quarter turn counter clockwise, the right column becoming the top row.
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        let columns:Vec<Vec<T>> = self.columns().map(|column| column.cloned().collect()).collect();
+        let cells = columns.into_iter().rev().flatten().collect();
+        Grid { width: self.height, height: self.width, cells }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!("{} is outside the {}x{} grid", point, self.width, self.height)
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Some(cell) => cell,
+            None => panic!("{} is outside the {}x{} grid", point, width, height)
+        }
+    }
+}
+
+/// Prints the rows top row first, so the grid reads the same as the input it came from.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::grid::Grid;
+    use crate::domain::point::Point;
+
+    fn sample() -> Grid<char> {
+        Grid::parse("abc\ndef", "a letter", |c| c.is_ascii_lowercase().then_some(c)).unwrap()
+    }
+
+    #[test]
+    fn first_line_is_the_top_row() {
+        let grid = sample();
+
+        assert_eq!(grid[Point::new(0, 1)], 'a');
+        assert_eq!(grid[Point::new(2, 0)], 'f');
+        assert_eq!(grid.size(), Point::new(3, 2));
+    }
+
+    #[test]
+    fn points_outside_are_none() {
+        let grid = sample();
+
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+    }
+
+    #[test]
+    fn parse_points_at_unexpected_characters() {
+        let error = Grid::parse("abc\ndEf", "a letter", |c| c.is_ascii_lowercase().then_some(c)).err().unwrap();
+
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.column, Some(2));
+        assert_eq!(error.message, "expected a letter, found 'E'");
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        let error = Grid::parse("abc\nde", "a letter", |c| Some(c)).err().unwrap();
+
+        assert_eq!(error.message, "expected a row of 3 tiles, found 2");
+    }
+
+    #[test]
+    fn neighbors_stay_inside() {
+        let grid = sample();
+
+        let neighbors:Vec<Point> = grid.neighbors(Point::new(0, 1)).collect();
+        let all:Vec<Point> = grid.all_neighbors(Point::new(0, 1)).collect();
+
+        assert_eq!(neighbors, vec![Point::new(0, 0), Point::new(1, 1)]);
+        assert_eq!(all.len(), 3);
+    }
+
+    #[test]
+    fn rows_and_columns_read_like_the_input() {
+        let grid = sample();
+
+        let rows:Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        let columns:Vec<String> = grid.columns().map(|column| column.collect()).collect();
+
+        assert_eq!(rows, vec!["abc", "def"]);
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+        assert_eq!(grid.row(0).unwrap(), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).unwrap().collect::<String>(), "be");
+    }
+
+    #[test]
+    fn can_transpose_and_rotate() {
+        let grid = sample();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+    }
+
+    #[test]
+    fn points_follow_reading_order() {
+        let grid = sample();
+
+        assert_eq!(grid.points().next(), Some(Point::new(0, 1)));
+        assert_eq!(grid.position(|c| *c == 'e'), Some(Point::new(1, 0)));
+    }
+}
@@ -1,7 +1,8 @@
 use std::cmp::{min, Ordering};
 use std::fmt;
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Add, Div, Mul, Neg, Sub};
 use itertools::max;
+use num::{Num, Signed};
 use num::integer::Roots;
 use crate::tools::usize_to_i32;
 
@@ -14,19 +15,74 @@ pub const NORTHWEST:Point = Point { x: -1, y: 1 };
 pub const SOUTHEAST:Point = Point { x: 1, y: -1 };
 pub const NORTHEAST:Point = Point { x: 1, y: 1 };
 
+/// For coordinates or distances that outgrow an `i32`.
+pub type PointLarge = Point<i64>;
+
+/// Points use maths coordinates: x grows to the east and y grows to the north, so `NORTH` is up.
+/// Puzzle inputs are written the other way, first line at the top, so go through `from_screen`
+/// and `to_screen` when turning a column and row read off the page into a point and back.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
-pub struct Point {
-    pub x: i32,
-    pub y: i32
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T
 }
-impl Point {
-    #[allow(dead_code)]
-    pub fn new(x: i32, y: i32) -> Self {
-        return Point { x, y }
+
+impl<T: Num + Copy> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+
+    pub fn scale(&self, value: T) -> Self {
+        Point::new(self.x * value, self.y * value)
+    }
+
+    pub fn dot(&self, other: Point<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z part of the cross product, twice the signed area of the triangle the two points make
+    /// with the origin.
+    pub fn cross(&self, other: Point<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// The same point with wider coordinates, like an `i32` point as an `i64` one.
+    pub fn cast<U: From<T>>(&self) -> Point<U> {
+        Point { x: U::from(self.x), y: U::from(self.y) }
+    }
+}
+
+impl<T: Num + Signed + Copy + PartialOrd> Point<T> {
+    pub fn manhattan_distance(&self, other: Point<T>) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
     }
+
+    /// The distance a king moves on a chess board, where diagonal steps count as one.
+    pub fn chebyshev_distance(&self, other: Point<T>) -> T {
+        let (dx, dy) = ((self.x - other.x).abs(), (self.y - other.y).abs());
+        if dx > dy { dx } else { dy }
+    }
+}
+
+impl Point {
     pub fn parse(x: usize, y: usize) -> Self {
         return Point { x: usize_to_i32(x).unwrap(), y: usize_to_i32(y).unwrap() }
     }
+
+    /// The point for `column` and `row` of a page `height` rows tall, rows counted from the top.
+    pub fn from_screen(column: usize, row: usize, height: usize) -> Self {
+        Point::parse(column, height - 1 - row)
+    }
+
+    /// The column and row of the page this point is drawn at, or `None` if it is off the page.
+    pub fn to_screen(&self, height: usize) -> Option<(usize, usize)> {
+        if self.x < 0 || self.y < 0 || self.y as usize >= height {
+            return None;
+        }
+
+        Some((self.x as usize, height - 1 - self.y as usize))
+    }
+
     pub fn get_neighbors(&self) -> Vec<Point> {
         let directions = [NORTH, EAST, WEST, SOUTH, NORTHEAST, NORTHWEST, SOUTHEAST, SOUTHWEST];
         return directions.iter().map(|dir| *dir + *self).collect();
@@ -45,17 +101,9 @@ impl Point {
     pub fn magnitude(&self) -> i32 {
         (self.x * self.x + self.y * self.y).sqrt()
     }
-
-    pub fn manhattan_distance(&self, other:Point) -> i32 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
-    }
-
-    pub fn scale(&self, value: i32) -> Self {
-        Point::new(self.x * value, self.y * value)
-    }
 }
 
-impl Add for Point {
+impl<T: Num + Copy> Add for Point<T> {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
@@ -63,7 +111,7 @@ impl Add for Point {
     }
 }
 
-impl Sub for Point {
+impl<T: Num + Copy> Sub for Point<T> {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self {
@@ -71,9 +119,71 @@ impl Sub for Point {
     }
 }
 
-impl fmt::Display for Point {
+impl<T: Num + Copy + Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {x: -self.x, y: -self.y}
+    }
+}
+
+impl<T: Num + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, value: T) -> Self {
+        self.scale(value)
+    }
+}
+
+impl<T: Num + Copy> Div<T> for Point<T> {
+    type Output = Self;
+
+    fn div(self, value: T) -> Self {
+        Self {x: self.x / value, y: self.y / value}
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "x: {}, y: {}", self.x, self.y)
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::domain::point::{NORTH, Point, PointLarge};
+
+    #[test]
+    fn can_measure_distances() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+    }
+
+    #[test]
+    fn can_multiply_points() {
+        let a = Point::new(2, 3);
+        let b = Point::new(-1, 4);
+
+        assert_eq!(a.dot(b), 10);
+        assert_eq!(a.cross(b), 11);
+        assert_eq!(a * 3, Point::new(6, 9));
+        assert_eq!(-NORTH, Point::new(0, -1));
+    }
+
+    #[test]
+    fn can_widen_coordinates() {
+        let wide:PointLarge = Point::new(i32::MAX, 1).cast();
+
+        assert_eq!((wide * 2).x, 2 * i32::MAX as i64);
+    }
+
+    #[test]
+    fn float_points_work_too() {
+        let a = Point::new(0.5, 1.5);
+
+        assert_eq!(a.manhattan_distance(Point::new(1.0, 1.0)), 1.0);
+    }
+}
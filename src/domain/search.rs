@@ -0,0 +1,320 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use priority_queue::PriorityQueue;
+
+/// Something to search through, described by the moves out of each node.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The nodes one move away from `node`, with what each move costs.
+    fn neighbors(&self, node: &Self::Node) -> Vec<(Self::Node, u64)>;
+
+    /// A guess at the cost left from `node` to a goal for `a_star`. It must never guess high, or
+    /// the route found might not be the cheapest. The default of 0 makes A* a plain Dijkstra.
+    fn heuristic(&self, _node: &Self::Node) -> u64 {
+        0
+    }
+}
+
+/// A way through a graph, from the start node to the goal node inclusive, and what it cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route<N> {
+    pub cost: u64,
+    pub path: Vec<N>
+}
+
+/// The route with the fewest moves, ignoring what they cost.
+pub fn bfs<G: Graph>(graph: &G, start: G::Node, is_goal: impl Fn(&G::Node) -> bool) -> Option<Route<G::Node>> {
+    let mut came_from:HashMap<G::Node, G::Node> = HashMap::new();
+    let mut seen:HashSet<G::Node> = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            let path = rebuild_path(&came_from, node);
+            return Some(Route { cost: path.len() as u64 - 1, path });
+        }
+
+        for (next, _) in graph.neighbors(&node) {
+            if seen.insert(next.clone()) {
+                came_from.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// How many moves it takes to reach every node no more than `limit` moves from the start.
+pub fn bfs_distances<G: Graph>(graph: &G, start: G::Node, limit: u64) -> HashMap<G::Node, u64> {
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        if distance == limit {
+            continue;
+        }
+
+        for (next, _) in graph.neighbors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// The route with the fewest moves between `start` and `goal`, searching out from both ends and
+/// stopping where they meet. Only for graphs where every move can be made backwards too.
+pub fn bidirectional_bfs<G: Graph>(graph: &G, start: G::Node, goal: G::Node) -> Option<Route<G::Node>> {
+    if start == goal {
+        return Some(Route { cost: 0, path: vec![start] });
+    }
+
+    let mut forward_from:HashMap<G::Node, Option<G::Node>> = HashMap::from([(start.clone(), None)]);
+    let mut backward_from:HashMap<G::Node, Option<G::Node>> = HashMap::from([(goal.clone(), None)]);
+    let mut forward = vec![start];
+    let mut backward = vec![goal];
+
+    while !forward.is_empty() && !backward.is_empty() {
+        // Grow whichever side has the smaller frontier, a whole layer at a time
+        let forward_turn = forward.len() <= backward.len();
+        let (frontier, came_from, other_came_from) = match forward_turn {
+            true => (&mut forward, &mut forward_from, &backward_from),
+            false => (&mut backward, &mut backward_from, &forward_from)
+        };
+
+        let mut next_frontier = Vec::new();
+        let mut meeting = None;
+        for node in frontier.iter() {
+            for (next, _) in graph.neighbors(node) {
+                if came_from.contains_key(&next) {
+                    continue;
+                }
+                came_from.insert(next.clone(), Some(node.clone()));
+                if other_came_from.contains_key(&next) {
+                    meeting = Some(next.clone());
+                    break;
+                }
+                next_frontier.push(next);
+            }
+            if meeting.is_some() {
+                break;
+            }
+        }
+
+        if let Some(meeting) = meeting {
+            let mut path = walk_back(&forward_from, meeting.clone());
+            path.reverse();
+            path.extend(walk_back(&backward_from, meeting).into_iter().skip(1));
+            return Some(Route { cost: path.len() as u64 - 1, path });
+        }
+
+        *frontier = next_frontier;
+    }
+
+    None
+}
+
+/// The cheapest route, trying the cheapest unfinished route first.
+pub fn dijkstra<G: Graph>(graph: &G, start: G::Node, is_goal: impl Fn(&G::Node) -> bool) -> Option<Route<G::Node>> {
+    best_first(graph, start, is_goal, false)
+}
+
+/// The cheapest route, like `dijkstra` but using the graph's heuristic to try routes heading for
+/// the goal first.
+pub fn a_star<G: Graph>(graph: &G, start: G::Node, is_goal: impl Fn(&G::Node) -> bool) -> Option<Route<G::Node>> {
+    best_first(graph, start, is_goal, true)
+}
+
+fn best_first<G: Graph>(graph: &G, start: G::Node, is_goal: impl Fn(&G::Node) -> bool, use_heuristic: bool) -> Option<Route<G::Node>> {
+    let estimate = |node: &G::Node| if use_heuristic { graph.heuristic(node) } else { 0 };
+    let mut frontier:PriorityQueue<G::Node, Reverse<u64>> = PriorityQueue::new();
+    let mut costs:HashMap<G::Node, u64> = HashMap::from([(start.clone(), 0)]);
+    let mut came_from:HashMap<G::Node, G::Node> = HashMap::new();
+    frontier.push(start.clone(), Reverse(estimate(&start)));
+
+    while let Some((node, _)) = frontier.pop() {
+        let cost = costs[&node];
+        if is_goal(&node) {
+            return Some(Route { cost, path: rebuild_path(&came_from, node) });
+        }
+
+        for (next, step) in graph.neighbors(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).map_or(true, |known| next_cost < *known) {
+                costs.insert(next.clone(), next_cost);
+                came_from.insert(next.clone(), node.clone());
+                frontier.push_increase(next.clone(), Reverse(next_cost + estimate(&next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// The dearest route that never visits a node twice. This tries every such route, so it is only
+/// quick on small graphs or ones with few branches.
+pub fn longest_path<G: Graph>(graph: &G, start: G::Node, is_goal: impl Fn(&G::Node) -> bool) -> Option<Route<G::Node>> {
+    if is_goal(&start) {
+        return Some(Route { cost: 0, path: vec![start] });
+    }
+
+    let mut best:Option<Route<G::Node>> = None;
+    let mut path = vec![start.clone()];
+    let mut steps:Vec<u64> = vec![0];
+    let mut on_path:HashSet<G::Node> = HashSet::from([start.clone()]);
+    let mut cost = 0;
+    // The moves still to try out of each node on the path. Walked with a stack rather than
+    // recursion as the paths through a puzzle can be thousands of nodes long.
+    let mut untried = vec![graph.neighbors(&start)];
+
+    while let Some(moves) = untried.last_mut() {
+        match moves.pop() {
+            Some((next, step)) => {
+                if on_path.contains(&next) {
+                    continue;
+                }
+
+                cost += step;
+                steps.push(step);
+                path.push(next.clone());
+                on_path.insert(next.clone());
+
+                if is_goal(&next) {
+                    if best.as_ref().map_or(true, |route| cost > route.cost) {
+                        best = Some(Route { cost, path: path.clone() });
+                    }
+                    untried.push(vec![]);
+                } else {
+                    untried.push(graph.neighbors(&next));
+                }
+            }
+            None => {
+                untried.pop();
+                if let Some(node) = path.pop() {
+                    on_path.remove(&node);
+                    cost -= steps.pop().unwrap_or(0);
+                }
+            }
+        }
+    }
+
+    best
+}
+
+fn rebuild_path<N: Clone + Eq + Hash>(came_from: &HashMap<N, N>, end: N) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(previous) = came_from.get(path.last().unwrap()) {
+        path.push(previous.clone());
+    }
+    path.reverse();
+
+    path
+}
+
+fn walk_back<N: Clone + Eq + Hash>(came_from: &HashMap<N, Option<N>>, from: N) -> Vec<N> {
+    let mut path = vec![from];
+    while let Some(Some(previous)) = came_from.get(path.last().unwrap()) {
+        path.push(previous.clone());
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::grid::Grid;
+    use crate::domain::point::Point;
+    use crate::domain::search::{a_star, bfs, bfs_distances, bidirectional_bfs, dijkstra, Graph, longest_path};
+
+    // Walls are #, anything else costs its digit to step onto
+    struct Maze {
+        grid: Grid<char>,
+        goal: Point
+    }
+
+    impl Graph for Maze {
+        type Node = Point;
+
+        fn neighbors(&self, node: &Point) -> Vec<(Point, u64)> {
+            self.grid.neighbors(*node)
+                .filter_map(|next| self.grid[next].to_digit(10).map(|cost| (next, cost as u64)))
+                .collect()
+        }
+
+        fn heuristic(&self, node: &Point) -> u64 {
+            node.manhattan_distance(self.goal) as u64
+        }
+    }
+
+    fn maze() -> Maze {
+        let grid = Grid::parse("1191\n1#11\n1111", "a digit or #", |c| (c == '#' || c.is_ascii_digit()).then_some(c)).unwrap();
+        Maze { grid, goal: Point::new(3, 2) }
+    }
+
+    #[test]
+    fn bfs_counts_moves() {
+        let maze = maze();
+
+        let route = bfs(&maze, Point::new(0, 2), |node| *node == maze.goal).unwrap();
+
+        assert_eq!(route.cost, 3);
+        assert_eq!(route.path.first(), Some(&Point::new(0, 2)));
+        assert_eq!(route.path.last(), Some(&Point::new(3, 2)));
+    }
+
+    #[test]
+    fn bfs_distances_stop_at_the_limit() {
+        let maze = maze();
+
+        let distances = bfs_distances(&maze, Point::new(0, 2), 2);
+
+        assert_eq!(distances.len(), 5);
+        assert_eq!(distances[&Point::new(0, 0)], 2);
+    }
+
+    #[test]
+    fn bidirectional_bfs_meets_in_the_middle() {
+        let maze = maze();
+
+        let route = bidirectional_bfs(&maze, Point::new(0, 0), Point::new(3, 2)).unwrap();
+
+        assert_eq!(route.cost, 5);
+        assert_eq!(route.path.len(), 6);
+        assert_eq!(route.path.first(), Some(&Point::new(0, 0)));
+        assert_eq!(route.path.last(), Some(&Point::new(3, 2)));
+        assert!(route.path.windows(2).all(|pair| pair[0].manhattan_distance(pair[1]) == 1));
+    }
+
+    #[test]
+    fn dijkstra_and_a_star_avoid_expensive_steps() {
+        let maze = maze();
+        let is_goal = |node: &Point| *node == maze.goal;
+
+        let slow = dijkstra(&maze, Point::new(0, 2), is_goal).unwrap();
+        let fast = a_star(&maze, Point::new(0, 2), is_goal).unwrap();
+
+        // Round the wall and up the right side rather than across the 9
+        assert_eq!(slow.cost, 7);
+        assert_eq!(fast.cost, 7);
+        assert_eq!(fast.path.len(), 8);
+    }
+
+    #[test]
+    fn longest_path_never_revisits() {
+        let maze = maze();
+
+        let route = longest_path(&maze, Point::new(0, 2), |node| *node == Point::new(1, 2)).unwrap();
+
+        // All the way round the wall and back along the top through the 9. One cell has to be
+        // left out, as moves alternate between the two colours of a chess board.
+        assert_eq!(route.path.len(), 10);
+        assert_eq!(route.cost, 17);
+    }
+}
@@ -1,26 +1,72 @@
 use std::fmt;
-use std::ops::{Add, Sub};
-use crate::domain::point::Point;
-use crate::tools::parse_numbers;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+use num::{Num, Signed};
+use crate::error::ParseError;
+use crate::tools::{next_chunk, parse_number};
+
+/// For coordinates or distances that outgrow an `i32`.
+pub type Vector3Large = Vector3<i64>;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
-pub struct Vector3 {
-    pub x: i32,
-    pub y: i32,
-    pub z: i32
+pub struct Vector3<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T
 }
 
-impl Vector3 {
-    pub fn parse(input_line: &str) -> Self {
-        let [x,y,z] = input_line.split(",").map(|val| parse_numbers(val).unwrap().1).next_chunk().unwrap();
-        Vector3 { x, y, z }
+impl<T: FromStr> Vector3<T> {
+    /// Reads `x,y,z` from `coordinates`, a slice of `line` so errors can point into it.
+    pub fn parse(line: &str, coordinates: &str) -> Result<Self, ParseError> {
+        let mut values = coordinates.split(",");
+        match (next_chunk(&mut values), values.next()) {
+            (Some([x,y,z]), None) => Ok(Vector3 { x: parse_number(line, x)?, y: parse_number(line, y)?, z: parse_number(line, z)? }),
+            _ => Err(ParseError::at(line, coordinates, format!("expected x,y,z, found {:?}", coordinates.trim())))
+        }
     }
+}
 
-    pub fn new(x:i32, y: i32, z: i32) -> Self {
+impl<T: Num + Copy> Vector3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
         Vector3 { x, y, z }
     }
+
+    pub fn scale(&self, value: T) -> Self {
+        Vector3::new(self.x * value, self.y * value, self.z * value)
+    }
+
+    pub fn dot(&self, other: Vector3<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: Vector3<T>) -> Self {
+        Vector3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x
+        )
+    }
+
+    /// The same vector with wider coordinates, like an `i32` vector as an `i64` one.
+    pub fn cast<U: From<T>>(&self) -> Vector3<U> {
+        Vector3 { x: U::from(self.x), y: U::from(self.y), z: U::from(self.z) }
+    }
 }
-impl Add for Vector3 {
+
+impl<T: Num + Signed + Copy + PartialOrd> Vector3<T> {
+    pub fn manhattan_distance(&self, other: Vector3<T>) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// The largest distance along any one axis.
+    pub fn chebyshev_distance(&self, other: Vector3<T>) -> T {
+        [(self.y - other.y).abs(), (self.z - other.z).abs()]
+            .into_iter()
+            .fold((self.x - other.x).abs(), |largest, distance| if distance > largest { distance } else { largest })
+    }
+}
+
+impl<T: Num + Copy> Add for Vector3<T> {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
@@ -28,9 +74,7 @@ impl Add for Vector3 {
     }
 }
 
-
-
-impl Sub for Vector3 {
+impl<T: Num + Copy> Sub for Vector3<T> {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self {
@@ -38,8 +82,67 @@ impl Sub for Vector3 {
     }
 }
 
-impl fmt::Display for Vector3 {
+impl<T: Num + Copy + Neg<Output = T>> Neg for Vector3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {x: -self.x, y: -self.y, z: -self.z }
+    }
+}
+
+impl<T: Num + Copy> Mul<T> for Vector3<T> {
+    type Output = Self;
+
+    fn mul(self, value: T) -> Self {
+        self.scale(value)
+    }
+}
+
+impl<T: Num + Copy> Div<T> for Vector3<T> {
+    type Output = Self;
+
+    fn div(self, value: T) -> Self {
+        Self {x: self.x / value, y: self.y / value, z: self.z / value }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vector3<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "x: {}, y: {}, z: {}", self.x, self.y, self.z)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::vector3::{Vector3, Vector3Large};
+
+    #[test]
+    fn can_parse_either_width() {
+        let line = "19, 13, 30 @ -2,  1, -2";
+
+        let position:Vector3 = Vector3::parse(line, &line[..10]).unwrap();
+        let velocity:Vector3Large = Vector3::parse(line, &line[13..]).unwrap();
+
+        assert_eq!(position, Vector3::new(19, 13, 30));
+        assert_eq!(velocity, Vector3::new(-2, 1, -2));
+    }
+
+    #[test]
+    fn can_multiply_vectors() {
+        let x = Vector3::new(1, 0, 0);
+        let y = Vector3::new(0, 1, 0);
+
+        assert_eq!(x.cross(y), Vector3::new(0, 0, 1));
+        assert_eq!(x.dot(y), 0);
+        assert_eq!(-(x * 4), Vector3::new(-4, 0, 0));
+    }
+
+    #[test]
+    fn can_measure_distances() {
+        let a = Vector3::new(1, 2, 3);
+        let b = Vector3::new(4, -2, 3);
+
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+    }
+}
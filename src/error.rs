@@ -0,0 +1,174 @@
+use std::fmt;
+
+/// Raised when a puzzle input can't be turned into a day's typed input. Whoever finds the problem
+/// fills in what it knows, the callers above it add the line and the day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub day: Option<u32>,
+    /// Counted from 1, like an editor would
+    pub line: Option<usize>,
+    /// Counted from 1, in characters
+    pub column: Option<usize>,
+    /// The offending line
+    pub text: Option<String>,
+    pub message: String
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        ParseError { day: None, line: None, column: None, text: None, message: message.into() }
+    }
+
+    /// An error about `token`, which should be a slice of `line` so its column can be worked out.
+    pub fn at(line: &str, token: &str, message: impl Into<String>) -> Self {
+        ParseError { column: column_of(line, token), text: Some(line.to_string()), ..ParseError::new(message) }
+    }
+
+    /// The line ran out before `expected` showed up.
+    pub fn missing(line: &str, expected: &str) -> Self {
+        ParseError::at(line, &line[line.len()..], format!("expected {}", expected))
+    }
+
+    pub fn nom(line: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => {
+                ParseError::at(line, error.input, format!("could not parse {:?}", error.input))
+            }
+            nom::Err::Incomplete(_) => ParseError::missing(line, "more input")
+        }
+    }
+
+    /// Sets the line number, unless a parser further down already did.
+    pub fn on_line(mut self, number: usize) -> Self {
+        self.line.get_or_insert(number);
+        self
+    }
+
+    /// For errors found in `block`, a slice of `input` parsed on its own, turns the line number
+    /// within the block into one within the input.
+    pub fn within(mut self, input: &str, block: &str) -> Self {
+        if let Some(line) = &mut self.line {
+            *line += line_number(input, block) - 1;
+        }
+        self
+    }
+
+    pub fn in_day(mut self, day: u32) -> Self {
+        self.day.get_or_insert(day);
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location:Vec<String> = [
+            self.day.map(|day| format!("day {}", day)),
+            self.line.map(|line| format!("line {}", line)),
+            self.column.map(|column| format!("column {}", column))
+        ].into_iter().flatten().collect();
+
+        if location.is_empty() {
+            write!(f, "{}", self.message)?;
+        } else {
+            write!(f, "{}: {}", location.join(", "), self.message)?;
+        }
+
+        if let Some(text) = &self.text {
+            write!(f, "\n    {}", text)?;
+            if let Some(column) = self.column {
+                write!(f, "\n    {}^", " ".repeat(column - 1))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses every line of `input`, numbering the line of the first one that fails.
+pub fn parse_lines<'a, T>(input: &'a str, mut parse: impl FnMut(&'a str) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError> {
+    input.lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|error| error.on_line(index + 1)))
+        .collect()
+}
+
+/// Which line of `input` the slice `line` starts on, for inputs parsed a block at a time.
+pub fn line_number(input: &str, line: &str) -> usize {
+    let offset = (line.as_ptr() as usize).saturating_sub(input.as_ptr() as usize).min(input.len());
+    input.as_bytes()[..offset].iter().filter(|byte| **byte == b'\n').count() + 1
+}
+
+fn column_of(line: &str, token: &str) -> Option<usize> {
+    let offset = (token.as_ptr() as usize).checked_sub(line.as_ptr() as usize)?;
+    if offset > line.len() || !line.is_char_boundary(offset) {
+        return None;
+    }
+
+    Some(line[..offset].chars().count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{line_number, parse_lines, ParseError};
+
+    #[test]
+    fn points_at_the_offending_token() {
+        let line = "R x (#70c710)";
+
+        let error = ParseError::at(line, &line[2..3], "expected a number, found \"x\"").on_line(3).in_day(18);
+
+        assert_eq!(error.column, Some(3));
+        assert_eq!(error.to_string(), "day 18, line 3, column 3: expected a number, found \"x\"\n    R x (#70c710)\n      ^");
+    }
+
+    #[test]
+    fn tokens_from_elsewhere_have_no_column() {
+        let token = String::from("6");
+
+        let error = ParseError::at("R 6 (#70c710)", &token, "not from this line");
+
+        assert_eq!(error.column, None);
+    }
+
+    #[test]
+    fn missing_points_past_the_end() {
+        let error = ParseError::missing("32T3K", "a bid");
+
+        assert_eq!(error.column, Some(6));
+        assert_eq!(error.message, "expected a bid");
+    }
+
+    #[test]
+    fn lines_are_numbered_from_one() {
+        let result = parse_lines("1\n2\nthree\n4", |line| line.parse::<u32>().map_err(|_| ParseError::at(line, line, "not a number")));
+
+        assert_eq!(result.unwrap_err().line, Some(3));
+    }
+
+    #[test]
+    fn inner_line_numbers_are_kept() {
+        let error = ParseError::new("bad").on_line(2).on_line(7);
+
+        assert_eq!(error.line, Some(2));
+    }
+
+    #[test]
+    fn block_line_numbers_move_with_the_block() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2";
+        let block = input.split("\n\n").nth(1).unwrap();
+
+        let error = ParseError::new("bad").on_line(2).within(input, block);
+
+        assert_eq!(error.line, Some(4));
+    }
+
+    #[test]
+    fn can_find_line_number_of_block() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2";
+        let block = input.split("\n\n").nth(1).unwrap();
+
+        assert_eq!(line_number(input, block), 3);
+    }
+}
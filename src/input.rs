@@ -0,0 +1,149 @@
+use std::{env, fmt, fs, io};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+pub static INPUT_DIR_VARIABLE: &str = "AOC_INPUT_DIR";
+static DEFAULT_INPUT_DIR: &str = "puzzle_inputs";
+
+/// Where the puzzle input for a run should come from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    /// Look the day up in the input directory.
+    PuzzleDirectory,
+    File(PathBuf),
+    Stdin
+}
+
+impl InputSource {
+    /// `-` reads from stdin, anything else is treated as a file path.
+    pub fn from_argument(argument: Option<&str>) -> Self {
+        match argument {
+            None => InputSource::PuzzleDirectory,
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path))
+        }
+    }
+}
+
+pub struct PuzzleInput {
+    pub content: String,
+    pub path: String
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing { day: u32, tried: Vec<PathBuf> },
+    Unreadable { path: String, error: io::Error }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Missing { day, tried } => {
+                let paths:Vec<String> = tried.iter().map(|path| path.display().to_string()).collect();
+                write!(f, "No input found for day {}, tried: {}", day, paths.join(", "))
+            }
+            InputError::Unreadable { path, error } => write!(f, "Could not read input {}: {}", path, error)
+        }
+    }
+}
+
+pub fn resolve_input(day: u32, source: &InputSource) -> Result<PuzzleInput, InputError> {
+    match source {
+        InputSource::PuzzleDirectory => {
+            let tried = candidate_paths(&input_directory(), day);
+            match tried.iter().find(|path| path.exists()) {
+                Some(path) => read_file(path),
+                None => Err(InputError::Missing { day, tried })
+            }
+        }
+        InputSource::File(path) => read_file(path),
+        InputSource::Stdin => {
+            let mut content = String::new();
+            io::stdin().read_to_string(&mut content)
+                .map_err(|error| InputError::Unreadable { path: "-".to_string(), error })?;
+            Ok(PuzzleInput { content, path: "-".to_string() })
+        }
+    }
+}
+
+pub fn input_directory() -> PathBuf {
+    match env::var_os(INPUT_DIR_VARIABLE) {
+        Some(directory) => PathBuf::from(directory),
+        None => PathBuf::from(DEFAULT_INPUT_DIR)
+    }
+}
+
+// Early days were saved as day01_input.txt, later ones as day21_input.txt, so accept either
+pub fn candidate_paths(directory: &Path, day: u32) -> Vec<PathBuf> {
+    let mut paths = vec![directory.join(format!("day{:02}_input.txt", day))];
+    let unpadded = directory.join(format!("day{}_input.txt", day));
+    if !paths.contains(&unpadded) {
+        paths.push(unpadded);
+    }
+
+    paths
+}
+
+fn read_file(path: &Path) -> Result<PuzzleInput, InputError> {
+    let path_name = path.display().to_string();
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(PuzzleInput { content, path: path_name }),
+        Err(error) => Err(InputError::Unreadable { path: path_name, error })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use crate::input::{candidate_paths, InputError, InputSource, resolve_input};
+
+    #[test]
+    fn candidates_cover_both_naming_schemes() {
+        let paths = candidate_paths(Path::new("inputs"), 7);
+
+        assert_eq!(paths, vec![PathBuf::from("inputs/day07_input.txt"), PathBuf::from("inputs/day7_input.txt")]);
+    }
+
+    #[test]
+    fn two_digit_days_only_have_one_candidate() {
+        let paths = candidate_paths(Path::new("inputs"), 21);
+
+        assert_eq!(paths, vec![PathBuf::from("inputs/day21_input.txt")]);
+    }
+
+    #[test]
+    fn argument_selects_source() {
+        assert_eq!(InputSource::from_argument(None), InputSource::PuzzleDirectory);
+        assert_eq!(InputSource::from_argument(Some("-")), InputSource::Stdin);
+        assert_eq!(InputSource::from_argument(Some("example.txt")), InputSource::File(PathBuf::from("example.txt")));
+    }
+
+    #[test]
+    fn can_read_explicit_file() {
+        let path = std::env::temp_dir().join("aoc_2023_input_test.txt");
+        fs::write(&path, "1abc2").unwrap();
+
+        let input = resolve_input(1, &InputSource::File(path.clone())).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(input.content, "1abc2");
+        assert_eq!(input.path, path.display().to_string());
+    }
+
+    #[test]
+    fn missing_file_error_names_the_path() {
+        let error = resolve_input(1, &InputSource::File(PathBuf::from("does/not/exist.txt"))).err().unwrap();
+
+        assert!(matches!(error, InputError::Unreadable { .. }));
+        assert!(error.to_string().contains("does/not/exist.txt"));
+    }
+
+    #[test]
+    fn missing_error_lists_paths_tried() {
+        let error = InputError::Missing { day: 7, tried: candidate_paths(Path::new("inputs"), 7) };
+
+        assert_eq!(error.to_string(), "No input found for day 7, tried: inputs/day07_input.txt, inputs/day7_input.txt");
+    }
+}
@@ -0,0 +1,22 @@
+#![allow(warnings)]
+
+//! Advent of Code 2023 solutions as a library.
+//!
+//! Every day lives in [`days`] with a `parse` step and `part_one`/`part_two` solvers working on its
+//! parsed input, and is registered as a [`solution::Puzzle`] in [`days::all_solutions`]. The shared
+//! geometry is in [`domain`] and small parsing and conversion helpers in [`tools`]. The rest is what
+//! the command line runner is built from.
+
+pub mod days;
+pub mod tools;
+pub mod domain;
+pub mod solution;
+pub mod error;
+pub mod answer;
+pub mod input;
+pub mod part_table;
+pub mod verify;
+pub mod selection;
+pub mod runner;
+pub mod report;
+pub mod bench;
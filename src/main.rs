@@ -1,107 +1,321 @@
-#![feature(iter_array_chunks)]
-#![feature(iter_next_chunk)]
 #![allow(warnings)]
 
 
-use crate::days::*;
-use std::{env, fs};
-use std::fmt::Display;
-use std::path::{Path, PathBuf};
-use std::time::Instant;
-use clap::Parser;
+use advent_of_code_2023::bench::{Baseline, bench_part, change_from, DEFAULT_BASELINE_FILE, format_stats};
+use advent_of_code_2023::days::{all_solutions, get_solution};
+use advent_of_code_2023::solution::{Part, Puzzle};
+use advent_of_code_2023::input::{InputSource, resolve_input};
+use advent_of_code_2023::report::{Format, reporter_for};
+use advent_of_code_2023::runner::{Job, missing_input, PartRecord, run_ordered, run_part, SharedInput};
+use advent_of_code_2023::selection::{DayList, parse_day_list, parse_part, parse_timeout};
+use advent_of_code_2023::verify::{DEFAULT_ANSWERS_FILE, ExpectedAnswers, Verdict};
+use std::{env, panic, process, thread};
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use clap::{Args, Parser, Subcommand};
+use clap::builder::RangedU64ValueParser;
 
 
-pub mod days;
-mod tools;
-mod domain;
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    run: RunArgument
+}
 
-static ANSI_ITALIC: &str = "\x1b[3m";
-static ANSI_BOLD: &str = "\x1b[1m";
-static ANSI_RESET: &str = "\x1b[0m";
+#[derive(Subcommand)]
+enum Command {
+    /// Time each part over many runs, optionally against a saved baseline
+    Bench(BenchArgument)
+}
 
-#[derive(Parser)]
+// Which days and parts to run, and on what input, shared by running and benchmarking
+#[derive(Args)]
+struct Selection {
+    /// Days to run as a list of days and ranges, e.g. `3,7,10-14`. Runs every day when left out
+    #[arg(value_parser = parse_day_list)]
+    days: Option<DayList>,
+    /// Skip these days, e.g. `17,21,23`
+    #[arg(long, value_parser = parse_day_list)]
+    exclude: Option<DayList>,
+    /// Only run part 1 or part 2
+    #[arg(long, value_parser = parse_part)]
+    part: Option<Part>,
+    /// Read the input from this file instead of the puzzle directory, `-` reads stdin
+    #[arg(long, requires = "days")]
+    input: Option<String>
+}
+
+impl Selection {
+    fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::BOTH.to_vec()
+        }
+    }
+}
+
+#[derive(Args)]
 struct RunArgument {
-    day: Option<i32>
+    #[command(flatten)]
+    selection: Selection,
+    /// Check the answers against the answers file, failing on any mismatch
+    #[arg(long, conflicts_with = "record")]
+    verify: bool,
+    /// Write the answers of this run into the answers file
+    #[arg(long)]
+    record: bool,
+    /// Answers file used to check, verify and record results
+    #[arg(long, default_value = DEFAULT_ANSWERS_FILE)]
+    answers: PathBuf,
+    /// How to print the results
+    #[arg(long, value_enum, default_value_t = Format::Plain)]
+    format: Format,
+    /// Give up on a part after this long, e.g. `30s` or `2m`, and carry on with the rest. A part
+    /// can't be stopped, so one that timed out keeps running in the background until the run ends
+    #[arg(long, value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+    /// Run this many parts at once, results are still printed in day order
+    #[arg(long, short, default_value_t = 1, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    jobs: usize
 }
 
-macro_rules! print_style_result {
-    ($day:path, $input:expr, $day_name:expr) => {{
-        use $day::*;
-        println!("----");
-        println!("🎄 {}{}{} 🎄", ANSI_BOLD, $day_name, ANSI_RESET);
-        println!("🎄 {}Part 1{} 🎄", ANSI_BOLD, ANSI_RESET);
-        print_result(part_one, $input);
-        println!("🎄 {}Part 2{} 🎄", ANSI_BOLD, ANSI_RESET);
-        print_result(part_two, $input);
-        println!("----");
-    }};
+#[derive(Args)]
+struct BenchArgument {
+    #[command(flatten)]
+    selection: Selection,
+    /// Timed runs of each part
+    #[arg(long, default_value_t = 10, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    iterations: usize,
+    /// Untimed runs of each part before timing starts
+    #[arg(long, default_value_t = 2)]
+    warmup: usize,
+    /// Baseline file to compare against or save to
+    #[arg(long, default_value = DEFAULT_BASELINE_FILE)]
+    baseline: PathBuf,
+    /// Save the medians of this run as the new baseline
+    #[arg(long, conflicts_with = "compare")]
+    save_baseline: bool,
+    /// Compare against the baseline, failing when a part got slower than the threshold allows
+    #[arg(long)]
+    compare: bool,
+    /// How much slower than the baseline a part may get, in percent
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64
 }
 
 fn main() {
-    let parse_result = RunArgument::parse();
+    let cli = Cli::parse();
 
-    match parse_result.day {
-        Some(day) => print_specific_day(day),
-        None => print_all_days()
+    // The report carries the panic message, so only point at where it came from
+    panic::set_hook(Box::new(|info| {
+        let thread = thread::current();
+        let location = info.location().map_or("an unknown location".to_string(), |location| location.to_string());
+        eprintln!("{} panicked at {}", thread.name().unwrap_or("solver"), location);
+    }));
+
+    match cli.command {
+        Some(Command::Bench(arguments)) => bench(arguments),
+        None => run(cli.run)
+    }
+}
+
+fn run(parse_result: RunArgument) {
+
+    let source = InputSource::from_argument(parse_result.selection.input.as_deref());
+    let solutions = select_or_exit(&parse_result.selection);
+    let parts = parse_result.selection.parts();
+    let mut expected = match ExpectedAnswers::load(&parse_result.answers) {
+        Ok(expected) => expected,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
+
+    let jobs = part_jobs(&solutions, &parts, &source, parse_result.timeout);
+    let mut reporter = reporter_for(parse_result.format, parse_result.verify);
+    let mut verdicts:Vec<Verdict> = Vec::new();
+    let mut recorded = 0;
+    let mut current_day = None;
+    let mut part_time = Duration::ZERO;
+
+    let timer = Instant::now();
+    run_ordered(jobs, parse_result.jobs, |record: PartRecord| {
+        let solution = solutions.iter().find(|solution| solution.day() == record.day).unwrap();
+        if current_day != Some(record.day) {
+            if current_day.is_some() {
+                reporter.end_day();
+            }
+            reporter.start_day(record.day, record.title);
+            current_day = Some(record.day);
+            part_time += record.parse_elapsed;
+        }
+
+        let expected_answer = expected.expected_for(solution.as_ref(), record.part);
+        reporter.part(&record, expected_answer.as_deref());
+        part_time += record.elapsed;
+
+        verdicts.push(Verdict::for_record(expected_answer, &record));
+        if let (Some(answer), true) = (&record.answer, parse_result.record) {
+            expected.record(record.day, record.part, answer);
+            recorded += 1;
+        }
+    });
+    if current_day.is_some() {
+        reporter.end_day();
+    }
+    reporter.finish();
+    eprintln!("Wall time {:.2?}, CPU time summed over parts {:.2?} ({} jobs)", timer.elapsed(), part_time, parse_result.jobs);
+
+    if parse_result.record {
+        match expected.save(&parse_result.answers) {
+            Ok(_) => eprintln!("Recorded {} answers to {}", recorded, parse_result.answers.display()),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        }
+    }
+
+    if parse_result.verify {
+        let passed = verdicts.iter().filter(|verdict| matches!(verdict, Verdict::Pass)).count();
+        let failed = verdicts.iter().filter(|verdict| matches!(verdict, Verdict::Fail { .. })).count();
+        let missing = verdicts.iter().filter(|verdict| matches!(verdict, Verdict::Missing)).count();
+        eprintln!("Verified {} parts: {} passed, {} failed, {} missing", verdicts.len(), passed, failed, missing);
+        if failed > 0 {
+            process::exit(1);
+        }
     }
     env::set_var("RUST_BACKTRACE", "1");
 }
 
-fn print_all_days(){
-    for i in 1..25 {
-        print_specific_day(i)
+fn bench(arguments: BenchArgument) {
+    let source = InputSource::from_argument(arguments.selection.input.as_deref());
+    let solutions = select_or_exit(&arguments.selection);
+    let baseline = match arguments.compare {
+        true => match Baseline::load(&arguments.baseline) {
+            Ok(baseline) => baseline,
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        },
+        false => Baseline::default()
+    };
+
+    let mut results = Baseline::default();
+    let mut regressions = 0;
+    for solution in &solutions {
+        println!("🎄 Day {}: {} 🎄", solution.day(), solution.title());
+        let input = match resolve_input(solution.day(), &source) {
+            Ok(input) => input,
+            Err(error) => {
+                println!("{}", error);
+                continue;
+            }
+        };
+
+        for part in arguments.selection.parts() {
+            let timed = panic::catch_unwind(AssertUnwindSafe(|| {
+                bench_part(solution.as_ref(), part, &input, arguments.warmup, arguments.iterations)
+            }));
+            let timed = match timed {
+                Ok(Ok(timed)) => timed,
+                Ok(Err(error)) => {
+                    println!("{}: parse error: {}", part, error);
+                    continue;
+                }
+                Err(_) => {
+                    println!("{}: panicked", part);
+                    continue;
+                }
+            };
+
+            println!("{} parse: {}", part, format_stats(&timed.parse));
+            println!("{} solve: {}", part, format_stats(&timed.solve));
+
+            if let Some(previous) = baseline.get(timed.day, part) {
+                let change = change_from(previous, timed.total.median);
+                let regressed = change * 100.0 > arguments.threshold;
+                regressions += regressed as usize;
+                println!(
+                    "{} median {:.2?} against {:.2?} in the baseline ({:+.1}%){}",
+                    part, timed.total.median, previous, change * 100.0, if regressed { " REGRESSION" } else { "" }
+                );
+            }
+            results.record(&timed);
+        }
+    }
+
+    if arguments.save_baseline {
+        match results.save(&arguments.baseline) {
+            Ok(_) => eprintln!("Saved baseline to {}", arguments.baseline.display()),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        }
+    }
+
+    if regressions > 0 {
+        eprintln!("{} parts got more than {}% slower than the baseline", regressions, arguments.threshold);
+        process::exit(1);
     }
 }
-fn print_specific_day(day: i32) {
-    let input = format!("day{}_input.txt", &day);
-    let info = format!("Day {}", &day);
-
-    match day {
-        1 => print_style_result!(day_01, load_file("day01_input.txt"), "Day 1"),
-        2 => print_style_result!(day_02, load_file("day02_input.txt"), "Day 2"),
-        3 => print_style_result!(day_03, load_file("day03_input.txt"), "Day 3"),
-        4 => print_style_result!(day_04, load_file("day04_input.txt"), "Day 4"),
-        5 => print_style_result!(day_05, load_file("day05_input.txt"), "Day 5"),
-        6 => print_style_result!(day_06, load_file("day06_input.txt"), "Day 6"),
-        7 => print_style_result!(day_07, load_file("day07_input.txt"), "Day 7"),
-        8 => print_style_result!(day_08, load_file("day08_input.txt"), "Day 8"),
-        9 => print_style_result!(day_09, load_file("day09_input.txt"), "Day 9"),
-        10 => print_style_result!(day_10, load_file("day10_input.txt"), "Day 10"),
-        11 => print_style_result!(day_11, load_file("day11_input.txt"), "Day 11"),
-        12 => print_style_result!(day_12, load_file("day12_input.txt"), "Day 12"),
-        13 => print_style_result!(day_13, load_file("day13_input.txt"), "Day 13"),
-        14 => print_style_result!(day_14, load_file("day14_input.txt"), "Day 14"),
-        15 => print_style_result!(day_15, load_file("day15_input.txt"), "Day 15"),
-        16 => print_style_result!(day_16, load_file("day16_input.txt"), "Day 16"),
-        17 => print_style_result!(day_17, load_file("day17_input.txt"), "Day 17"),
-        18 => print_style_result!(day_18, load_file("day18_input.txt"), "Day 18"),
-        19 => print_style_result!(day_19, load_file("day19_input.txt"), "Day 19"),
-        20 => print_style_result!(day_20, load_file("day20_input.txt"), "Day 20"),
-        21 => print_style_result!(day_21, load_file(&input), info),
-        22 => print_style_result!(day_22, load_file(&input), info),
-        23 => print_style_result!(day_23, load_file(&input), info),
-        24 => print_style_result!(day_24, load_file(&input), info),
-        _ => {}
+
+fn select_or_exit(selection: &Selection) -> Vec<Arc<dyn Puzzle>> {
+    match select_solutions(selection) {
+        Ok(solutions) => solutions,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
     }
 }
 
+fn select_solutions(arguments: &Selection) -> Result<Vec<Arc<dyn Puzzle>>, String> {
+    if let Some(days) = &arguments.days {
+        if let Some(day) = days.0.iter().find(|day| get_solution(**day).is_none()) {
+            return Err(format!("No solution registered for day {}", day));
+        }
+        if arguments.input.is_some() && days.0.len() != 1 {
+            return Err("--input can only be used when running a single day".to_string());
+        }
+    }
 
-fn print_result<T: Display>(func: impl FnOnce(String) -> T, input: String) {
-    let timer = Instant::now();
-    let result = func(input);
-    let time = timer.elapsed();
-    println!(
-        "{} {}(elapsed: {:.2?}){}",
-        result, ANSI_ITALIC, time, ANSI_RESET
-    );
+    Ok(all_solutions()
+        .into_iter()
+        .filter(|solution| arguments.days.as_ref().map_or(true, |days| days.contains(solution.day())))
+        .filter(|solution| !arguments.exclude.as_ref().map_or(false, |days| days.contains(solution.day())))
+        .collect())
 }
 
-fn load_file(path: &str) -> String {
-    let file_path = Path::new("puzzle_inputs").join(path);
+// Inputs are read up front, as stdin can only be read once and every part of a day shares its input
+fn part_jobs(solutions: &Vec<Arc<dyn Puzzle>>, parts: &Vec<Part>, source: &InputSource, timeout: Option<Duration>) -> Vec<Job<'static, PartRecord>> {
+    let mut jobs:Vec<Job<PartRecord>> = Vec::new();
 
-    if !file_path.exists() {
-        panic!("failure");
+    for solution in solutions {
+        match resolve_input(solution.day(), source) {
+            Ok(input) => {
+                let input = Arc::new(SharedInput::new(input));
+                for part in parts.iter().copied() {
+                    let (solution, input) = (solution.clone(), input.clone());
+                    jobs.push(Box::new(move || run_part(solution, part, input, timeout)));
+                }
+            }
+            Err(error) => {
+                for part in parts.iter().copied() {
+                    let record = missing_input(solution.as_ref(), part, &error);
+                    jobs.push(Box::new(move || record));
+                }
+            }
+        }
     }
 
-    return fs::read_to_string(file_path).unwrap();
-}
\ No newline at end of file
+    jobs
+}
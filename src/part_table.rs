@@ -0,0 +1,78 @@
+//! The toml layout shared by the answers file, the benchmark baseline and the example answers:
+//! `part_one` and `part_two` keys, grouped under `[dayNN]` sections when a file covers many days.
+
+use std::collections::BTreeMap;
+use toml::{Table, Value};
+use crate::solution::Part;
+
+/// Reads the parts in `table`, called `name` in errors. `read` turns a value into what's wanted,
+/// or gives None when it is the wrong kind, which `expected` describes.
+pub fn read_parts<T>(name: &str, table: &Table, expected: &str, read: impl Fn(&Value) -> Option<T>) -> Result<Vec<(Part, T)>, String> {
+    if let Some(key) = table.keys().find(|key| !Part::BOTH.iter().any(|part| part.key() == key.as_str())) {
+        return Err(format!("{} has an unknown key {}", name, key));
+    }
+
+    Part::BOTH.into_iter()
+        .filter_map(|part| table.get(part.key()).map(|value| (part, value)))
+        .map(|(part, value)| read(value).map(|read| (part, read)).ok_or(format!("{}.{} should be {}", name, part.key(), expected)))
+        .collect()
+}
+
+/// Reads a file of `[dayNN]` sections, each holding the parts of that day.
+pub fn read_days<T>(content: &str, expected: &str, read: impl Fn(&Value) -> Option<T>) -> Result<BTreeMap<(u32, Part), T>, String> {
+    let table = content.parse::<Table>().map_err(|error| error.to_string())?;
+    let mut values = BTreeMap::new();
+
+    for (key, parts) in &table {
+        let day = key.strip_prefix("day")
+            .and_then(|day| day.parse::<u32>().ok())
+            .ok_or(format!("unknown section [{}]", key))?;
+        let parts = parts.as_table().ok_or(format!("[{}] should be a table", key))?;
+
+        for (part, value) in read_parts(key, parts, expected, &read)? {
+            values.insert((day, part), value);
+        }
+    }
+
+    Ok(values)
+}
+
+/// Writes the values back out as `[dayNN]` sections, in day order.
+pub fn write_days<T>(values: &BTreeMap<(u32, Part), T>, write: impl Fn(&T) -> Value) -> String {
+    let mut table = Table::new();
+    for ((day, part), value) in values {
+        let section = table.entry(format!("day{:02}", day)).or_insert(Value::Table(Table::new()));
+        section.as_table_mut().unwrap().insert(part.key().to_string(), write(value));
+    }
+
+    toml::to_string(&table).unwrap()
+}
+
+/// An answer, written as a string so big numbers survive, but a plain integer is taken too.
+pub fn read_answer(value: &Value) -> Option<String> {
+    match value {
+        Value::String(answer) => Some(answer.clone()),
+        Value::Integer(answer) => Some(answer.to_string()),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::part_table::{read_answer, read_days, write_days};
+    use crate::solution::Part;
+
+    #[test]
+    fn days_round_trip() {
+        let values = read_days("[day07]\npart_two = 5\n\n[day01]\npart_one = \"54\"", "an answer", read_answer).unwrap();
+
+        assert_eq!(values.get(&(7, Part::Two)).map(|answer| answer.as_str()), Some("5"));
+        assert_eq!(write_days(&values, |answer| answer.clone().into()), "[day01]\npart_one = \"54\"\n\n[day07]\npart_two = \"5\"\n");
+    }
+
+    #[test]
+    fn unknown_keys_and_values_are_rejected() {
+        assert_eq!(read_days("[day01]\npart_three = \"1\"", "an answer", read_answer), Err("day01 has an unknown key part_three".to_string()));
+        assert_eq!(read_days("[day01]\npart_one = true", "an answer", read_answer), Err("day01.part_one should be an answer".to_string()));
+    }
+}
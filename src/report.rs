@@ -0,0 +1,245 @@
+use std::io::{self, IsTerminal};
+use clap::ValueEnum;
+use crate::runner::{PartRecord, Status};
+use crate::verify::Verdict;
+
+static ANSI_ITALIC: &str = "\x1b[3m";
+static ANSI_BOLD: &str = "\x1b[1m";
+static ANSI_RESET: &str = "\x1b[0m";
+
+static CSV_HEADER: &str = "day,title,part,status,answer,elapsed_ns,parse_ns,input,message";
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Banner per day, meant for reading in a terminal
+    Plain,
+    /// A json array with one object per day and part
+    Json,
+    /// A header row, then one row per day and part
+    Csv
+}
+
+/// Receives the results of a run as they come in. Days are opened and closed around their parts.
+pub trait Reporter {
+    fn start_day(&mut self, _day: u32, _title: &str) {}
+    fn part(&mut self, record: &PartRecord, expected: Option<&str>);
+    fn end_day(&mut self) {}
+    fn finish(&mut self) {}
+}
+
+pub fn reporter_for(format: Format, verify: bool) -> Box<dyn Reporter> {
+    match format {
+        Format::Plain => Box::new(PlainReporter { ansi: io::stdout().is_terminal(), verify, input_reported: false }),
+        Format::Json => Box::new(JsonReporter { records: 0 }),
+        Format::Csv => Box::new(CsvReporter { header_written: false })
+    }
+}
+
+struct PlainReporter {
+    ansi: bool,
+    verify: bool,
+    input_reported: bool
+}
+
+impl PlainReporter {
+    fn style(&self, code: &'static str) -> &'static str {
+        if self.ansi { code } else { "" }
+    }
+}
+
+impl Reporter for PlainReporter {
+    fn start_day(&mut self, day: u32, title: &str) {
+        self.input_reported = false;
+        println!("----");
+        println!("🎄 {}Day {}: {}{} 🎄", self.style(ANSI_BOLD), day, title, self.style(ANSI_RESET));
+    }
+
+    fn part(&mut self, record: &PartRecord, expected: Option<&str>) {
+        // Both parts share the input, so only complain about it once
+        if let Status::MissingInput(message) = &record.status {
+            if !self.input_reported {
+                println!("{}", message);
+                self.input_reported = true;
+            }
+            if self.verify {
+                println!("{} [{}]", record.part, Verdict::Missing);
+            }
+            return;
+        }
+
+        println!("🎄 {}{}{} 🎄", self.style(ANSI_BOLD), record.part, self.style(ANSI_RESET));
+        let result = match (&record.answer, &record.status) {
+            (Some(answer), _) => {
+                let check = match (Verdict::check(expected.map(|expected| expected.to_string()), answer), self.verify) {
+                    (verdict, true) => format!(" [{}]", verdict),
+                    (Verdict::Pass, false) => " ✅".to_string(),
+                    (Verdict::Fail { expected }, false) => format!(" ❌ (expected {})", expected),
+                    (Verdict::Missing, false) => "".to_string()
+                };
+                let width = if answer.exceeds_64_bits() { " [exceeds 64 bits]" } else { "" };
+                format!("{}{}{}", answer, width, check)
+            }
+            (None, status) => status.to_string()
+        };
+        // Parse errors quote the offending line underneath, keep the timings on the first line
+        let (result, detail) = result.split_once('\n').map_or((result.as_str(), None), |(first, rest)| (first, Some(rest)));
+        println!(
+            "{} {}(elapsed: {:.2?}, parse: {:.2?}){}",
+            result, self.style(ANSI_ITALIC), record.elapsed, record.parse_elapsed, self.style(ANSI_RESET)
+        );
+        if let Some(detail) = detail {
+            println!("{}", detail);
+        }
+    }
+
+    fn end_day(&mut self) {
+        println!("----");
+    }
+}
+
+struct JsonReporter {
+    records: usize
+}
+
+impl Reporter for JsonReporter {
+    fn part(&mut self, record: &PartRecord, _expected: Option<&str>) {
+        let separator = if self.records == 0 { "[" } else { "," };
+        println!("{}{}", separator, json_record(record));
+        self.records += 1;
+    }
+
+    fn finish(&mut self) {
+        println!("{}", if self.records == 0 { "[]" } else { "]" });
+    }
+}
+
+struct CsvReporter {
+    header_written: bool
+}
+
+impl CsvReporter {
+    fn write_header(&mut self) {
+        if !self.header_written {
+            println!("{}", CSV_HEADER);
+            self.header_written = true;
+        }
+    }
+}
+
+impl Reporter for CsvReporter {
+    fn part(&mut self, record: &PartRecord, _expected: Option<&str>) {
+        self.write_header();
+        println!("{}", csv_record(record));
+    }
+
+    fn finish(&mut self) {
+        self.write_header();
+    }
+}
+
+// Answers are written as strings since u128 results don't survive a round trip through a json number
+pub fn json_record(record: &PartRecord) -> String {
+    format!(
+        "{{\"day\":{},\"title\":{},\"part\":{},\"status\":{},\"answer\":{},\"elapsed_ns\":{},\"parse_ns\":{},\"input\":{},\"message\":{}}}",
+        record.day,
+        json_string(record.title),
+        record.part.number(),
+        json_string(record.status.name()),
+        json_optional(record.answer.as_ref().map(|answer| answer.to_string()).as_deref()),
+        record.elapsed.as_nanos(),
+        record.parse_elapsed.as_nanos(),
+        json_optional(record.input_path.as_deref()),
+        json_optional(record.status.message().as_deref())
+    )
+}
+
+pub fn csv_record(record: &PartRecord) -> String {
+    [
+        record.day.to_string(),
+        csv_field(record.title),
+        record.part.number().to_string(),
+        csv_field(record.status.name()),
+        csv_field(&record.answer.as_ref().map(|answer| answer.to_string()).unwrap_or_default()),
+        record.elapsed.as_nanos().to_string(),
+        record.parse_elapsed.as_nanos().to_string(),
+        csv_field(record.input_path.as_deref().unwrap_or_default()),
+        csv_field(&record.status.message().unwrap_or_default())
+    ].join(",")
+}
+
+fn json_optional(value: Option<&str>) -> String {
+    value.map_or("null".to_string(), json_string)
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            character if character.is_control() => escaped.push_str(&format!("\\u{:04x}", character as u32)),
+            character => escaped.push(character)
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::answer::Answer;
+    use crate::report::{csv_field, csv_record, json_record, json_string};
+    use crate::runner::{PartRecord, Status};
+    use crate::solution::Part;
+
+    fn record(answer: Option<Answer>, status: Status) -> PartRecord {
+        PartRecord {
+            day: 7,
+            title: "Camel Cards",
+            part: Part::Two,
+            answer,
+            elapsed: Duration::from_nanos(1500),
+            parse_elapsed: Duration::from_nanos(200),
+            input_path: Some("puzzle_inputs/day07_input.txt".to_string()),
+            status
+        }
+    }
+
+    #[test]
+    fn can_write_json_record() {
+        let json = json_record(&record(Some(Answer::from(250665248u32)), Status::Ok));
+
+        assert_eq!(json, r#"{"day":7,"title":"Camel Cards","part":2,"status":"ok","answer":"250665248","elapsed_ns":1500,"parse_ns":200,"input":"puzzle_inputs/day07_input.txt","message":null}"#);
+    }
+
+    #[test]
+    fn can_write_csv_record() {
+        let csv = csv_record(&record(None, Status::Panicked("not parsable, \"A\"".to_string())));
+
+        assert_eq!(csv, r#"7,Camel Cards,2,panic,,1500,200,puzzle_inputs/day07_input.txt,"panicked: not parsable, ""A""""#);
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(json_string("Aplenty \"x\"\n\\"), r#""Aplenty \"x\"\n\\""#);
+        assert_eq!(json_string("\u{1b}[3m"), r#""\u001b[3m""#);
+    }
+
+    #[test]
+    fn csv_fields_are_only_quoted_when_needed() {
+        assert_eq!(csv_field("Trebuchet?!"), "Trebuchet?!");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+    }
+}
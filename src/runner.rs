@@ -0,0 +1,351 @@
+use std::any::Any;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::{InputError, PuzzleInput};
+use crate::solution::{ParsedInput, Part, Puzzle};
+
+/// How running a single part ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Ok,
+    ParseFailed(String),
+    Panicked(String),
+    /// The solver found the input has no answer, without panicking
+    Unsolved(String),
+    TimedOut(Duration),
+    MissingInput(String)
+}
+
+impl Status {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::ParseFailed(_) => "parse error",
+            Status::Panicked(_) => "panic",
+            Status::Unsolved(_) => "unsolved",
+            Status::TimedOut(_) => "timeout",
+            Status::MissingInput(_) => "missing input"
+        }
+    }
+
+    pub fn message(&self) -> Option<String> {
+        match self {
+            Status::Ok => None,
+            status => Some(status.to_string())
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::ParseFailed(message) => write!(f, "parse error: {}", message),
+            Status::Panicked(message) => write!(f, "panicked: {}", message),
+            Status::Unsolved(message) => write!(f, "unsolved: {}", message),
+            Status::TimedOut(limit) => write!(f, "timed out after {:.2?}", limit),
+            Status::MissingInput(message) => write!(f, "{}", message)
+        }
+    }
+}
+
+/// Everything reported about one part of one day.
+#[derive(Debug, Clone)]
+pub struct PartRecord {
+    pub day: u32,
+    pub title: &'static str,
+    pub part: Part,
+    pub answer: Option<Answer>,
+    /// Time spent solving, the shared parse is in `parse_elapsed`
+    pub elapsed: Duration,
+    pub parse_elapsed: Duration,
+    pub input_path: Option<String>,
+    pub status: Status
+}
+
+/// A day's puzzle input, parsed at most once however many of its parts run.
+pub struct SharedInput {
+    pub input: PuzzleInput,
+    parsed: OnceLock<(Result<ParsedInput, ParseError>, Duration)>
+}
+
+impl SharedInput {
+    pub fn new(input: PuzzleInput) -> Self {
+        SharedInput { input, parsed: OnceLock::new() }
+    }
+
+    /// The parse result and how long it took. Parts running at the same time wait on whichever
+    /// of them got here first.
+    pub fn parsed(&self, solution: &dyn Puzzle) -> &(Result<ParsedInput, ParseError>, Duration) {
+        self.parsed.get_or_init(|| {
+            let timer = Instant::now();
+            let parsed = solution.parse(&self.input.content);
+            (parsed, timer.elapsed())
+        })
+    }
+}
+
+// The same as the main thread gets. The deepest recursion left, day 12's record splitting and day
+// 22's chain reactions, is a few thousand small frames at most.
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Runs one part on a worker thread so a panic only loses that part. When the timeout passes the
+/// worker is left to finish in the background, as there's no way to stop it, and the part is
+/// reported as timed out.
+pub fn run_part(solution: Arc<dyn Puzzle>, part: Part, input: Arc<SharedInput>, timeout: Option<Duration>) -> PartRecord {
+    let (day, title) = (solution.day(), solution.title());
+    let input_path = Some(input.input.path.clone());
+    let (sender, receiver) = mpsc::channel();
+
+    let timer = Instant::now();
+    let worker = thread::Builder::new()
+        .name(format!("day {} {}", day, part))
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || {
+            let (parsed, parse_time) = input.parsed(solution.as_ref());
+            let solved = parsed.as_ref().map_err(|error| error.clone()).map(|parsed| {
+                let timer = Instant::now();
+                let answer = solution.solve(part, parsed);
+                (answer, timer.elapsed())
+            });
+            let _ = sender.send((solved, *parse_time));
+        })
+        .expect("could not start a worker thread");
+
+    let received = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+    };
+
+    let (answer, elapsed, parse_elapsed, status) = match received {
+        Ok((Ok((Answer::Unsolved(reason), solve_time)), parse_time)) => (None, solve_time, parse_time, Status::Unsolved(reason)),
+        Ok((Ok((answer, solve_time)), parse_time)) => (Some(answer), solve_time, parse_time, Status::Ok),
+        Ok((Err(error), parse_time)) => (None, Duration::ZERO, parse_time, Status::ParseFailed(error.to_string())),
+        Err(RecvTimeoutError::Timeout) => (None, timer.elapsed(), Duration::ZERO, Status::TimedOut(timeout.unwrap())),
+        // The sender only goes away without sending when the parser or solver panicked
+        Err(RecvTimeoutError::Disconnected) => {
+            let message = match worker.join() {
+                Err(payload) => panic_message(payload.as_ref()),
+                Ok(_) => "worker stopped without an answer".to_string()
+            };
+            (None, timer.elapsed(), Duration::ZERO, Status::Panicked(message))
+        }
+    };
+
+    PartRecord { day, title, part, answer, elapsed, parse_elapsed, input_path, status }
+}
+
+pub type Job<'a, T> = Box<dyn FnOnce() -> T + Send + 'a>;
+
+/// Runs the jobs on a pool of `workers` threads, handing every result to `handle` in the order the
+/// jobs were given as soon as all the ones before it are done.
+pub fn run_ordered<'a, T: Send>(jobs: Vec<Job<'a, T>>, workers: usize, mut handle: impl FnMut(T)) {
+    let queue = Mutex::new(jobs.into_iter().enumerate().collect::<VecDeque<_>>());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers.max(1) {
+            let (queue, sender) = (&queue, sender.clone());
+            scope.spawn(move || {
+                loop {
+                    let next = queue.lock().unwrap().pop_front();
+                    match next {
+                        Some((index, job)) => { let _ = sender.send((index, job())); },
+                        None => break
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut finished = BTreeMap::new();
+        let mut next = 0;
+        for (index, result) in receiver {
+            finished.insert(index, result);
+            while let Some(result) = finished.remove(&next) {
+                handle(result);
+                next += 1;
+            }
+        }
+    });
+}
+
+pub fn missing_input(solution: &dyn Puzzle, part: Part, error: &InputError) -> PartRecord {
+    PartRecord {
+        day: solution.day(),
+        title: solution.title(),
+        part,
+        answer: None,
+        elapsed: Duration::ZERO,
+        parse_elapsed: Duration::ZERO,
+        input_path: None,
+        status: Status::MissingInput(error.to_string())
+    }
+}
+
+// `panic!` with a literal carries a &str, with format arguments a String
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use crate::answer::Answer;
+    use crate::error::ParseError;
+    use crate::input::PuzzleInput;
+    use crate::runner::{Job, run_ordered, run_part, SharedInput, Status};
+    use crate::solution::{Part, Solution};
+
+    struct Unparsable;
+
+    impl Solution for Unparsable {
+        type Input = String;
+
+        fn day(&self) -> u32 {
+            99
+        }
+
+        fn title(&self) -> &'static str {
+            "Unparsable"
+        }
+
+        fn parse(&self, input: &str) -> Result<String, ParseError> {
+            match input {
+                "" => Err(ParseError::new("empty input")),
+                input => Ok(input.to_string())
+            }
+        }
+
+        fn part_one(&self, input: &String) -> Answer {
+            input.trim().parse::<u32>().unwrap().into()
+        }
+
+        fn part_two(&self, input: &String) -> Answer {
+            if input == "none" {
+                return Err::<u32, String>("nothing to find".to_string()).into();
+            }
+            if input == "forever" {
+                loop {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+            }
+            panic!("not parsable: {}", input)
+        }
+    }
+
+    // Counts its own parses, so other tests running at the same time can't add to the count
+    struct Counted(Arc<AtomicUsize>);
+
+    impl Solution for Counted {
+        type Input = ();
+
+        fn day(&self) -> u32 {
+            98
+        }
+
+        fn title(&self) -> &'static str {
+            "Counted"
+        }
+
+        fn parse(&self, _input: &str) -> Result<(), ParseError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn part_one(&self, _input: &()) -> Answer {
+            1u32.into()
+        }
+
+        fn part_two(&self, _input: &()) -> Answer {
+            2u32.into()
+        }
+    }
+
+    fn input(content: &str) -> Arc<SharedInput> {
+        Arc::new(SharedInput::new(PuzzleInput { content: content.to_string(), path: "example.txt".to_string() }))
+    }
+
+    #[test]
+    fn answers_are_recorded() {
+        let record = run_part(Arc::new(Unparsable), Part::One, input("42"), None);
+
+        assert_eq!(record.status, Status::Ok);
+        assert_eq!(record.answer, Some(Answer::from(42u32)));
+        assert_eq!(record.input_path.as_deref(), Some("example.txt"));
+    }
+
+    #[test]
+    fn parts_share_a_single_parse() {
+        let shared = input("42");
+        let parses = Arc::new(AtomicUsize::new(0));
+
+        run_part(Arc::new(Counted(parses.clone())), Part::One, shared.clone(), None);
+        run_part(Arc::new(Counted(parses.clone())), Part::Two, shared, None);
+
+        assert_eq!(parses.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn parse_errors_are_kept_apart_from_panics() {
+        let record = run_part(Arc::new(Unparsable), Part::One, input(""), None);
+
+        assert_eq!(record.status, Status::ParseFailed("day 99: empty input".to_string()));
+        assert_eq!(record.status.name(), "parse error");
+    }
+
+    #[test]
+    fn panics_are_caught_with_their_message() {
+        let record = run_part(Arc::new(Unparsable), Part::Two, input("abc"), Some(Duration::from_secs(10)));
+
+        assert_eq!(record.status, Status::Panicked("not parsable: abc".to_string()));
+        assert_eq!(record.status.to_string(), "panicked: not parsable: abc");
+        assert_eq!(record.answer, None);
+    }
+
+    #[test]
+    fn unsolved_parts_are_not_answers() {
+        let record = run_part(Arc::new(Unparsable), Part::Two, input("none"), None);
+
+        assert_eq!(record.status, Status::Unsolved("nothing to find".to_string()));
+        assert_eq!(record.status.name(), "unsolved");
+        assert_eq!(record.answer, None);
+    }
+
+    #[test]
+    fn slow_parts_time_out() {
+        let record = run_part(Arc::new(Unparsable), Part::Two, input("forever"), Some(Duration::from_millis(50)));
+
+        assert_eq!(record.status, Status::TimedOut(Duration::from_millis(50)));
+        assert_eq!(record.answer, None);
+    }
+
+    #[test]
+    fn results_come_back_in_job_order() {
+        let jobs:Vec<Job<u64>> = (0..6u64)
+            .map(|index| Box::new(move || {
+                std::thread::sleep(Duration::from_millis(30 - index * 5));
+                index
+            }) as Job<u64>)
+            .collect();
+
+        let mut results = vec![];
+        run_ordered(jobs, 3, |result| results.push(result));
+
+        assert_eq!(results, vec![0, 1, 2, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+use crate::solution::Part;
+
+/// Advent runs from the 1st to the 25th of December.
+pub const DAYS: RangeInclusive<u32> = 1..=25;
+
+/// A set of days written as a comma separated list of days and inclusive ranges, e.g. `3,7,10-14`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayList(pub BTreeSet<u32>);
+
+impl DayList {
+    pub fn contains(&self, day: u32) -> bool {
+        self.0.contains(&day)
+    }
+}
+
+pub fn parse_day_list(input: &str) -> Result<DayList, String> {
+    let mut days = BTreeSet::new();
+
+    for entry in input.split(',').map(|entry| entry.trim()) {
+        match entry.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(format!("range {} runs backwards", entry));
+                }
+                days.extend(start..=end);
+            }
+            None => {
+                days.insert(parse_day(entry)?);
+            }
+        }
+    }
+
+    Ok(DayList(days))
+}
+
+// Checked against the calendar here, so a huge range is turned down before it is expanded
+fn parse_day(input: &str) -> Result<u32, String> {
+    match input.trim().parse::<u32>() {
+        Ok(day) if DAYS.contains(&day) => Ok(day),
+        Ok(day) => Err(format!("day {} is outside {} to {}", day, DAYS.start(), DAYS.end())),
+        _ => Err(format!("{:?} is not a day number", input))
+    }
+}
+
+pub fn parse_part(input: &str) -> Result<Part, String> {
+    match input.trim() {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("{:?} is not a part, use 1 or 2", input))
+    }
+}
+
+/// Seconds by default, or with a `ms`, `s` or `m` suffix, e.g. `90`, `500ms`, `2m`.
+pub fn parse_timeout(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    let (number, scale) = if let Some(number) = input.strip_suffix("ms") {
+        (number, 0.001)
+    } else if let Some(number) = input.strip_suffix('s') {
+        (number, 1.0)
+    } else if let Some(number) = input.strip_suffix('m') {
+        (number, 60.0)
+    } else {
+        (input, 1.0)
+    };
+
+    match number.trim().parse::<f64>() {
+        Ok(amount) if amount > 0.0 && amount.is_finite() => Ok(Duration::from_secs_f64(amount * scale)),
+        _ => Err(format!("{:?} is not a timeout, use e.g. 30, 500ms or 2m", input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::selection::{parse_day_list, parse_part, parse_timeout};
+    use crate::solution::Part;
+
+    #[test]
+    fn can_parse_days_and_ranges() {
+        let days = parse_day_list("3,7,10-14").unwrap();
+
+        assert_eq!(days.0.into_iter().collect::<Vec<u32>>(), vec![3, 7, 10, 11, 12, 13, 14]);
+    }
+
+    #[test]
+    fn overlapping_entries_are_merged() {
+        let days = parse_day_list("5, 4-6,5").unwrap();
+
+        assert_eq!(days.0.len(), 3);
+        assert_eq!(days.contains(4), true);
+    }
+
+    #[test]
+    fn bad_day_lists_are_rejected() {
+        assert_eq!(parse_day_list("0").is_err(), true);
+        assert_eq!(parse_day_list("7-3").is_err(), true);
+        assert_eq!(parse_day_list("3,,4").is_err(), true);
+        assert_eq!(parse_day_list("three").is_err(), true);
+    }
+
+    #[test]
+    fn days_past_christmas_are_rejected() {
+        assert_eq!(parse_day_list("26"), Err("day 26 is outside 1 to 25".to_string()));
+        assert_eq!(parse_day_list("1-4000000000").is_err(), true);
+        assert_eq!(parse_day_list("1-25").unwrap().0.len(), 25);
+    }
+
+    #[test]
+    fn can_parse_part() {
+        assert_eq!(parse_part("1"), Ok(Part::One));
+        assert_eq!(parse_part("2"), Ok(Part::Two));
+        assert_eq!(parse_part("3").is_err(), true);
+    }
+
+    #[test]
+    fn can_parse_timeouts() {
+        assert_eq!(parse_timeout("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_timeout("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_timeout("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_timeout("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_timeout("0").is_err(), true);
+        assert_eq!(parse_timeout("soon").is_err(), true);
+    }
+}
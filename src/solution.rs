@@ -0,0 +1,110 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+use crate::answer::Answer;
+use crate::error::ParseError;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Part::One => "part_one",
+            Part::Two => "part_two"
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Part {}", self.number())
+    }
+}
+
+/// A single day of the calendar. Every `days::day_XX` module implements this on a unit struct and
+/// registers it in `days::all_solutions`. The input is parsed once into `Input` and shared by both
+/// parts, so parse failures are told apart from the solver going wrong.
+pub trait Solution: Send + Sync {
+    type Input: Send + Sync + 'static;
+
+    fn day(&self) -> u32;
+
+    fn title(&self) -> &'static str;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_one(&self, input: &Self::Input) -> Answer;
+
+    fn part_two(&self, input: &Self::Input) -> Answer;
+
+    /// Known answers for the stored puzzle input, if they have been confirmed.
+    fn expected_answers(&self) -> (Option<&'static str>, Option<&'static str>) {
+        (None, None)
+    }
+}
+
+/// A day's parsed input with its type erased, so days with different inputs can share a registry.
+pub type ParsedInput = Arc<dyn Any + Send + Sync>;
+
+/// The object safe side of `Solution` that the registry, runner and benchmarks work with.
+pub trait Puzzle: Send + Sync {
+    fn day(&self) -> u32;
+
+    fn title(&self) -> &'static str;
+
+    fn parse(&self, input: &str) -> Result<ParsedInput, ParseError>;
+
+    /// Panics when handed input parsed by a different day.
+    fn solve(&self, part: Part, input: &ParsedInput) -> Answer;
+
+    fn expected_answer(&self, part: Part) -> Option<&'static str>;
+
+    /// Parses and solves in one go, for callers that only need the one part.
+    fn run(&self, part: Part, input: &str) -> Result<Answer, ParseError> {
+        let parsed = self.parse(input)?;
+        Ok(self.solve(part, &parsed))
+    }
+}
+
+impl<S: Solution> Puzzle for S {
+    fn day(&self) -> u32 {
+        Solution::day(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Solution::title(self)
+    }
+
+    fn parse(&self, input: &str) -> Result<ParsedInput, ParseError> {
+        let parsed = Solution::parse(self, input).map_err(|error| error.in_day(Solution::day(self)))?;
+        Ok(Arc::new(parsed))
+    }
+
+    fn solve(&self, part: Part, input: &ParsedInput) -> Answer {
+        let input = input.downcast_ref::<S::Input>().expect("input was parsed by another day");
+        match part {
+            Part::One => self.part_one(input),
+            Part::Two => self.part_two(input)
+        }
+    }
+
+    fn expected_answer(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.expected_answers().0,
+            Part::Two => self.expected_answers().1
+        }
+    }
+}
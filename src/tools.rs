@@ -1,5 +1,6 @@
 use std::str::FromStr;
 use nom::{IResult, combinator::{map_res, recognize, opt}, sequence::preceded, character::complete::digit1, bytes::complete::tag};
+use crate::error::ParseError;
 #[allow(dead_code)]
 pub fn is_digit(c: &char) -> bool {
     c.is_digit(10)
@@ -46,6 +47,25 @@ pub fn parse_numbers_f64(input: &str) -> IResult<&str, f64> {
     Ok((i, number))
 }
 
+/// Reads `token`, a slice of `line`, as a number, pointing at it when it isn't one.
+pub fn parse_number<T: FromStr>(line: &str, token: &str) -> Result<T, ParseError> {
+    let token = token.trim();
+    token.parse::<T>().map_err(|_| ParseError::at(line, token, format!("expected a number, found {:?}", token)))
+}
+
+/// Takes the next `N` items of `iter` as an array, or `None` when it runs out first. A stable
+/// stand-in for `Iterator::next_chunk`.
+pub fn next_chunk<const N: usize, I: Iterator>(iter: &mut I) -> Option<[I::Item; N]> {
+    let items:Vec<I::Item> = iter.by_ref().take(N).collect();
+    items.try_into().ok()
+}
+
+/// Yields the items of `iter` `N` at a time, dropping any shorter remainder at the end. A stable
+/// stand-in for `Iterator::array_chunks`.
+pub fn array_chunks<const N: usize, I: Iterator>(mut iter: I) -> impl Iterator<Item = [I::Item; N]> {
+    std::iter::from_fn(move || next_chunk(&mut iter))
+}
+
 pub fn usize_to_i32(num: usize) -> Result<i32, String> {
     // Check if the `usize` value can fit within the range of `i32`
     if num > i32::MAX as usize {
@@ -92,4 +112,31 @@ pub fn usize_to_i64(num: usize) -> Result<i64, String> {
     let converted_num = num as i64;
 
     Ok(converted_num)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::{array_chunks, next_chunk};
+
+    #[test]
+    fn next_chunk_takes_exactly_n() {
+        let mut iter = 1..=5;
+
+        assert_eq!(next_chunk(&mut iter), Some([1, 2, 3]));
+        assert_eq!(iter.next(), Some(4));
+    }
+
+    #[test]
+    fn next_chunk_is_none_when_too_short() {
+        let mut iter = 1..=2;
+
+        assert_eq!(next_chunk::<3, _>(&mut iter), None);
+    }
+
+    #[test]
+    fn array_chunks_drops_the_remainder() {
+        let chunks:Vec<[i32; 2]> = array_chunks(1..=5).collect();
+
+        assert_eq!(chunks, vec![[1, 2], [3, 4]]);
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use toml::Value;
+use crate::answer::Answer;
+use crate::part_table::{read_answer, read_days, write_days};
+use crate::runner::{PartRecord, Status};
+use crate::solution::{Part, Puzzle};
+
+pub static DEFAULT_ANSWERS_FILE: &str = "puzzle_inputs/answers.toml";
+
+/// Answers confirmed for the stored puzzle inputs, kept in a toml file keyed by day and part:
+///
+/// ```toml
+/// [day01]
+/// part_one = "54388"
+/// part_two = "53515"
+/// ```
+#[derive(Debug, Default)]
+pub struct ExpectedAnswers {
+    answers: BTreeMap<(u32, Part), String>
+}
+
+impl ExpectedAnswers {
+    /// A missing file is treated as having no recorded answers yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(ExpectedAnswers::default());
+        }
+
+        let content = fs::read_to_string(path).map_err(|error| format!("Could not read {}: {}", path.display(), error))?;
+        ExpectedAnswers::parse(&content).map_err(|error| format!("Could not parse {}: {}", path.display(), error))
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        Ok(ExpectedAnswers { answers: read_days(content, "a string", read_answer)? })
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|answer| answer.as_str())
+    }
+
+    /// Answers from the file take priority over the ones compiled into the solution.
+    pub fn expected_for(&self, solution: &dyn Puzzle, part: Part) -> Option<String> {
+        self.get(solution.day(), part)
+            .or(solution.expected_answer(part))
+            .map(|answer| answer.to_string())
+    }
+
+    pub fn record(&mut self, day: u32, part: Part, answer: &Answer) {
+        self.answers.insert((day, part), answer.to_string());
+    }
+
+    pub fn to_toml(&self) -> String {
+        write_days(&self.answers, |answer| Value::String(answer.clone()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_toml()).map_err(|error| format!("Could not write {}: {}", path.display(), error))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing
+}
+
+impl Verdict {
+    pub fn check(expected: Option<String>, answer: &Answer) -> Self {
+        match expected {
+            None => Verdict::Missing,
+            Some(expected) if &Answer::from(expected.as_str()) == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail { expected }
+        }
+    }
+
+    /// Like `check`, for a part however it ended. Without an input there's nothing to check, so
+    /// the part is missing, but running and giving no answer fails whatever was expected of it.
+    pub fn for_record(expected: Option<String>, record: &PartRecord) -> Self {
+        match (&record.answer, &record.status) {
+            (Some(answer), _) => Verdict::check(expected, answer),
+            (None, Status::MissingInput(_)) => Verdict::Missing,
+            (None, _) => Verdict::Fail { expected: expected.unwrap_or_default() }
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Verdict::Missing => write!(f, "missing")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::answer::Answer;
+    use crate::error::ParseError;
+    use crate::runner::{PartRecord, Status};
+    use crate::solution::{Part, Solution};
+    use crate::verify::{ExpectedAnswers, Verdict};
+
+    struct Known;
+
+    impl Solution for Known {
+        type Input = ();
+
+        fn day(&self) -> u32 {
+            1
+        }
+
+        fn title(&self) -> &'static str {
+            "Known"
+        }
+
+        fn parse(&self, _input: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part_one(&self, _input: &()) -> Answer {
+            1.into()
+        }
+
+        fn part_two(&self, _input: &()) -> Answer {
+            2.into()
+        }
+
+        fn expected_answers(&self) -> (Option<&'static str>, Option<&'static str>) {
+            (Some("1"), Some("2"))
+        }
+    }
+
+    #[test]
+    fn can_parse_answers_file() {
+        let input = r#"[day01]
+part_one = "54388"
+part_two = 53515
+
+[day25]
+part_one = "54""#;
+
+        let answers = ExpectedAnswers::parse(input).unwrap();
+
+        assert_eq!(answers.get(1, Part::One), Some("54388"));
+        assert_eq!(answers.get(1, Part::Two), Some("53515"));
+        assert_eq!(answers.get(25, Part::Two), None);
+    }
+
+    #[test]
+    fn unknown_sections_are_rejected() {
+        let result = ExpectedAnswers::parse("[dayone]\npart_one = \"1\"");
+
+        assert_eq!(result.is_err(), true);
+    }
+
+    #[test]
+    fn recorded_answers_round_trip() {
+        let mut answers = ExpectedAnswers::default();
+        answers.record(19, Part::Two, &Answer::from(167409079868000u128));
+        answers.record(3, Part::One, &Answer::from(4361));
+
+        let parsed = ExpectedAnswers::parse(&answers.to_toml()).unwrap();
+
+        assert_eq!(parsed.get(19, Part::Two), Some("167409079868000"));
+        assert_eq!(parsed.get(3, Part::One), Some("4361"));
+        assert!(answers.to_toml().starts_with("[day03]"));
+    }
+
+    #[test]
+    fn answers_file_overrides_the_solution() {
+        let answers = ExpectedAnswers::parse("[day01]\npart_two = \"3\"").unwrap();
+
+        assert_eq!(answers.expected_for(&Known, Part::One).as_deref(), Some("1"));
+        assert_eq!(answers.expected_for(&Known, Part::Two).as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn verdicts_compare_answers() {
+        assert_eq!(Verdict::check(Some("42".to_string()), &Answer::from(42u64)), Verdict::Pass);
+        assert_eq!(Verdict::check(Some("41".to_string()), &Answer::from(42u64)), Verdict::Fail { expected: "41".to_string() });
+        assert_eq!(Verdict::check(None, &Answer::from(42u64)), Verdict::Missing);
+    }
+
+    #[test]
+    fn parts_without_input_are_missing_not_failed() {
+        let record = |status: Status| PartRecord {
+            day: 10,
+            title: "Pipe Maze",
+            part: Part::One,
+            answer: None,
+            elapsed: Duration::ZERO,
+            parse_elapsed: Duration::ZERO,
+            input_path: None,
+            status
+        };
+
+        assert_eq!(Verdict::for_record(Some("6890".to_string()), &record(Status::MissingInput("no input".to_string()))), Verdict::Missing);
+        assert_eq!(Verdict::for_record(Some("6890".to_string()), &record(Status::Panicked("oops".to_string()))), Verdict::Fail { expected: "6890".to_string() });
+        assert_eq!(Verdict::for_record(None, &record(Status::TimedOut(Duration::from_secs(1)))), Verdict::Fail { expected: String::new() });
+    }
+}
@@ -0,0 +1,97 @@
+//! Checks every puzzle example in `puzzle_inputs/examples`. An example is a `dayNN_name.txt` input
+//! with a `dayNN_name.toml` next to it giving the expected `part_one` and/or `part_two` answers, so
+//! adding a case is just dropping the two files in.
+
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use advent_of_code_2023::days::get_solution;
+use advent_of_code_2023::part_table::{read_answer, read_parts};
+use advent_of_code_2023::solution::Part;
+use toml::Table;
+
+struct Example {
+    name: String,
+    day: u32,
+    input: String,
+    expected: Vec<(Part, String)>
+}
+
+fn example_directory() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("puzzle_inputs").join("examples")
+}
+
+fn load_example(input_path: &Path) -> Result<Example, String> {
+    let name = input_path.file_stem().unwrap().to_string_lossy().to_string();
+    let day = name.strip_prefix("day")
+        .and_then(|rest| rest.get(..2))
+        .and_then(|day| day.parse::<u32>().ok())
+        .ok_or(format!("{} should be named dayNN_<name>.txt", name))?;
+
+    let input = fs::read_to_string(input_path).map_err(|error| format!("could not read {}: {}", input_path.display(), error))?;
+    let answers_path = input_path.with_extension("toml");
+    let answers = fs::read_to_string(&answers_path).map_err(|error| format!("could not read {}: {}", answers_path.display(), error))?;
+    let table = answers.parse::<Table>().map_err(|error| format!("could not parse {}: {}", answers_path.display(), error))?;
+
+    let expected = read_parts(&answers_path.display().to_string(), &table, "a string", read_answer)?;
+    if expected.is_empty() {
+        return Err(format!("{} expects no answers", answers_path.display()));
+    }
+
+    Ok(Example { name, day, input, expected })
+}
+
+fn check_example(example: &Example) -> Vec<String> {
+    let Some(solution) = get_solution(example.day) else {
+        return vec![format!("{}: no solution registered for day {}", example.name, example.day)];
+    };
+    let parsed = match solution.parse(&example.input) {
+        Ok(parsed) => parsed,
+        Err(error) => return vec![format!("{}: {}", example.name, error)]
+    };
+
+    let mut failures = Vec::new();
+    for (part, expected) in &example.expected {
+        match panic::catch_unwind(AssertUnwindSafe(|| solution.solve(*part, &parsed))) {
+            Ok(answer) if answer.to_string() == *expected => {}
+            Ok(answer) => failures.push(format!("{} {}: expected {}, got {}", example.name, part, expected, answer)),
+            Err(_) => failures.push(format!("{} {}: panicked", example.name, part))
+        }
+    }
+
+    failures
+}
+
+#[test]
+fn every_example_gives_the_expected_answers() {
+    let mut inputs:Vec<PathBuf> = fs::read_dir(example_directory())
+        .expect("the examples directory should exist")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect();
+    inputs.sort();
+    assert!(!inputs.is_empty(), "no examples found in {}", example_directory().display());
+
+    let mut failures = Vec::new();
+    for input in &inputs {
+        match load_example(input) {
+            Ok(example) => failures.extend(check_example(&example)),
+            Err(error) => failures.push(error)
+        }
+    }
+
+    assert!(failures.is_empty(), "{} of {} examples went wrong:\n{}", failures.len(), inputs.len(), failures.join("\n"));
+}
+
+#[test]
+fn every_answers_file_has_an_input() {
+    let orphans:Vec<String> = fs::read_dir(example_directory())
+        .expect("the examples directory should exist")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "toml"))
+        .filter(|path| !path.with_extension("txt").exists())
+        .map(|path| path.display().to_string())
+        .collect();
+
+    assert!(orphans.is_empty(), "answers without an input: {}", orphans.join(", "));
+}